@@ -0,0 +1,1219 @@
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+
+use convex::Value as ConvexValue;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use serde_json::{json, Value as JsonValue};
+use syn::{parse_quote, Attribute, Ident, Item, LitFloat, LitInt, LitStr, Type};
+
+use crate::convex::{
+    describe_data_type, function_kind, id_table_name, literal_value, validate_type_name, ConvexColumn, ConvexFunction,
+    ConvexFunctions, ConvexSchema, ConvexTable,
+};
+use crate::errors::ConvexTypeGeneratorError;
+
+pub(crate) fn generate_code(path: &Path, data: (ConvexSchema, ConvexFunctions)) -> Result<(), ConvexTypeGeneratorError>
+{
+    // Rendered first, so invalid code leaves the previous output in place
+    let code = render_code(data)?;
+
+    let mut file = std::fs::File::create(path)?;
+
+    // Clear the file
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+
+    file.write_all(code.as_bytes())?;
+
+    Ok(())
+}
+
+/// Render the generated code for a parsed schema and its functions.
+///
+/// The items for each table and function are built with `quote!`, so a name that can't be a Rust
+/// identifier fails generation with the schema element it came from. The items are collected into a
+/// `syn::File` and pretty-printed with `prettyplease`.
+pub(crate) fn render_code(data: (ConvexSchema, ConvexFunctions)) -> Result<String, ConvexTypeGeneratorError>
+{
+    let file_header = r#"// This file is generated by convex-typegen. Do not modify directly.
+// You can find more information about convex-typegen at https://github.com/JamalLyons/convex-typegen
+
+"#;
+
+    let mut file = syn::File {
+        shebang: None,
+        attrs: Vec::new(),
+        items: Vec::new(),
+    };
+
+    // First generate all enums from the tables
+    for table in &data.0.tables {
+        push_items(&mut file, &format!("table `{}`", table.name), generate_table_enums(table))?;
+    }
+
+    // Then generate the table structs
+    for table in data.0.tables {
+        let element = format!("table `{}`", table.name);
+        push_items(&mut file, &element, generate_table_code(table))?;
+    }
+
+    // Generate function argument types
+    let type_names = function_type_names(&data.1);
+    for (function, type_name) in data.1.iter().zip(&type_names) {
+        let element = format!("function `{}:{}`", function.file_name, function.name);
+        push_items(&mut file, &element, generate_function_code(function, type_name))?;
+    }
+
+    // Generate the `api` and `internal` function references
+    push_items(
+        &mut file,
+        "the `api` and `internal` modules",
+        generate_function_references(&data.1, &type_names),
+    )?;
+
+    // Generate the registry of all functions
+    push_items(&mut file, "the `FUNCTIONS` registry", Ok(generate_function_registry(&data.1)))?;
+
+    // Generate the serializable enum of public function calls
+    push_items(&mut file, "the `ConvexCall` enum", generate_call_enum(&data.1, &type_names))?;
+
+    // Printed an item at a time, to keep a blank line between them
+    let items = file
+        .items
+        .into_iter()
+        .map(|item| {
+            prettyplease::unparse(&syn::File {
+                shebang: None,
+                attrs: Vec::new(),
+                items: vec![item],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(format!("{}{}", file_header, items.join("\n")))
+}
+
+/// Add the items generated for a schema element to the file.
+///
+/// Each item gets an `#[allow]` for the lints generated names trip on. These are outer attributes
+/// rather than a `#![allow]` header, which would be rejected when the file is pulled in with
+/// `include!`.
+fn push_items(
+    file: &mut syn::File,
+    element: &str,
+    items: Result<Vec<Item>, ConvexTypeGeneratorError>,
+) -> Result<(), ConvexTypeGeneratorError>
+{
+    let invalid = |details: String| ConvexTypeGeneratorError::InvalidGeneratedCode {
+        element: element.to_string(),
+        details,
+    };
+
+    let items = items.map_err(|error| match error {
+        // The generators don't know which element they were generating
+        ConvexTypeGeneratorError::InvalidGeneratedCode { details, .. } => invalid(details),
+        error => error,
+    })?;
+
+    for mut item in items {
+        if let Some(duplicate) = duplicate_name(&file.items, &item) {
+            return Err(invalid(format!("`{}` is defined more than once", duplicate)));
+        }
+        if let Some(attrs) = item_attrs(&mut item) {
+            attrs.insert(0, parse_quote!(#[allow(dead_code, non_camel_case_types, non_snake_case)]));
+        }
+        file.items.push(item);
+    }
+
+    Ok(())
+}
+
+/// Find a name that is a valid identifier but would not compile: a duplicate field or variant of the
+/// item, or a type or value already defined by one of the `existing` items.
+fn duplicate_name(existing: &[Item], item: &Item) -> Option<String>
+{
+    let members = match item {
+        Item::Struct(item) => item.fields.iter().filter_map(|field| field.ident.clone()).collect(),
+        Item::Enum(item) => item.variants.iter().map(|variant| variant.ident.clone()).collect(),
+        _ => Vec::new(),
+    };
+    for (index, member) in members.iter().enumerate() {
+        if members[..index].contains(member) {
+            return Some(member.to_string());
+        }
+    }
+
+    let name = item_name(item)?;
+    existing
+        .iter()
+        .any(|other| item_name(other) == Some(name))
+        .then(|| name.to_string())
+}
+
+/// The name a top-level item defines, if any.
+fn item_name(item: &Item) -> Option<&Ident>
+{
+    match item {
+        Item::Struct(item) => Some(&item.ident),
+        Item::Enum(item) => Some(&item.ident),
+        Item::Type(item) => Some(&item.ident),
+        Item::Static(item) => Some(&item.ident),
+        Item::Const(item) => Some(&item.ident),
+        Item::Fn(item) => Some(&item.sig.ident),
+        Item::Mod(item) => Some(&item.ident),
+        _ => None,
+    }
+}
+
+fn item_attrs(item: &mut Item) -> Option<&mut Vec<Attribute>>
+{
+    match item {
+        Item::Struct(item) => Some(&mut item.attrs),
+        Item::Enum(item) => Some(&mut item.attrs),
+        Item::Impl(item) => Some(&mut item.attrs),
+        Item::Type(item) => Some(&mut item.attrs),
+        Item::Static(item) => Some(&mut item.attrs),
+        Item::Const(item) => Some(&mut item.attrs),
+        Item::Fn(item) => Some(&mut item.attrs),
+        Item::Mod(item) => Some(&mut item.attrs),
+        _ => None,
+    }
+}
+
+/// Turn a generated name into an identifier.
+///
+/// # Errors
+/// Returns an error if the name is not a valid Rust identifier, e.g. a struct named after a table
+/// whose name contains a `$`.
+fn ident(name: &str) -> Result<Ident, ConvexTypeGeneratorError>
+{
+    syn::parse_str(name).map_err(|_| ConvexTypeGeneratorError::InvalidGeneratedCode {
+        // Filled in by `push_items`
+        element: String::new(),
+        details: format!("`{}` is not a valid identifier", name),
+    })
+}
+
+/// A string literal for the generated code
+fn lit_str(value: &str) -> LitStr
+{
+    LitStr::new(value, Span::call_site())
+}
+
+/// Generate enums for a table's union types
+fn generate_table_enums(table: &ConvexTable) -> Result<Vec<Item>, ConvexTypeGeneratorError>
+{
+    let mut items = Vec::new();
+
+    for column in &table.columns {
+        // Handle regular unions
+        if let Some("union") = column.data_type["type"].as_str() {
+            let enum_name = format!(
+                "{}{}",
+                capitalize_first_letter(&table.name),
+                capitalize_first_letter(&column.name)
+            );
+            items.push(generate_union_enum(&enum_name, &column.data_type, table, column)?);
+        }
+
+        // Handle optional unions
+        if let Some("optional") = column.data_type["type"].as_str() {
+            if let Some("union") = column.data_type["inner"]["type"].as_str() {
+                let enum_name = format!(
+                    "{}Optional{}",
+                    capitalize_first_letter(&table.name),
+                    capitalize_first_letter(&column.name)
+                );
+                items.push(generate_union_enum(&enum_name, &column.data_type["inner"], table, column)?);
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+/// Generate an enum for a union type.
+///
+/// Literal variants are matched by their string value. All other variants are untagged, so they
+/// are matched by shape and must come after the literals.
+fn generate_union_enum(
+    enum_name: &str,
+    union_type: &JsonValue,
+    table: &ConvexTable,
+    column: &ConvexColumn,
+) -> Result<Item, ConvexTypeGeneratorError>
+{
+    let mut literal_variants = Vec::new();
+    let mut untagged_variants = Vec::new();
+    // Distinct values can share a name, e.g. "in-progress" and "in_progress"
+    let mut variant_names = Vec::new();
+    let mut variant_name = |value: &str| {
+        let mut name = to_pascal_case(value);
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            name.insert(0, 'V');
+        }
+        while variant_names.contains(&name) {
+            name.push('_');
+        }
+        variant_names.push(name.clone());
+        ident(&name)
+    };
+
+    if let Some(variants) = union_type["variants"].as_array() {
+        for variant in variants {
+            match variant["type"].as_str() {
+                Some("literal") => {
+                    if let Some(value) = variant["value"]["value"].as_str() {
+                        let name = variant_name(value)?;
+                        literal_variants.push(quote! {
+                            #[serde(rename = #value)]
+                            #name
+                        });
+                    }
+                }
+                Some(type_name) => {
+                    let rust_type = convex_type_to_rust_type(variant, Some(&table.name), Some(&column.name))?;
+                    let name = variant_name(type_name)?;
+                    untagged_variants.push(quote! {
+                        #[serde(untagged)]
+                        #name(#rust_type)
+                    });
+                }
+                None => continue,
+            }
+        }
+    }
+
+    let enum_name = ident(enum_name)?;
+    Ok(parse_quote! {
+        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+        pub enum #enum_name {
+            #(#literal_variants,)*
+            #(#untagged_variants,)*
+        }
+    })
+}
+
+/// Generate the code for a table.
+fn generate_table_code(table: ConvexTable) -> Result<Vec<Item>, ConvexTypeGeneratorError>
+{
+    let table_struct_name = ident(&format!("{}Table", capitalize_first_letter(&table.name)))?;
+
+    // Generate fields for each column
+    let fields = table
+        .columns
+        .iter()
+        .map(|column| {
+            let rust_type = if column.data_type["type"].as_str() == Some("union") {
+                let enum_name = ident(&format!(
+                    "{}{}",
+                    capitalize_first_letter(&table.name),
+                    capitalize_first_letter(&column.name)
+                ))?;
+                parse_quote!(#enum_name)
+            } else {
+                convex_type_to_rust_type(&column.data_type, Some(&table.name), Some(&column.name))?
+            };
+            struct_field(&column.name, &column.data_type, rust_type)
+        })
+        .collect::<Result<Vec<_>, ConvexTypeGeneratorError>>()?;
+
+    let validator_methods = generate_validator_methods(
+        &table.name,
+        table.columns.iter().map(|column| (column.name.as_str(), &column.data_type)),
+    )?;
+
+    Ok(vec![
+        parse_quote! {
+            #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+            pub struct #table_struct_name {
+                #(#fields,)*
+            }
+        },
+        parse_quote! {
+            impl #table_struct_name {
+                #validator_methods
+            }
+        },
+    ])
+}
+
+/// Generate a public struct field, renamed for serde if its name isn't a plain Rust identifier
+fn struct_field(name: &str, data_type: &JsonValue, rust_type: Type) -> Result<TokenStream, ConvexTypeGeneratorError>
+{
+    let attribute = serde_field_attribute(data_type);
+    let field = rust_ident(name);
+    let rename = (field != name).then(|| quote!(#[serde(rename = #name)]));
+    let field = ident(&field)?;
+    Ok(quote! {
+        #attribute
+        #rename
+        pub #field: #rust_type
+    })
+}
+
+/// Convert a Convex type to its corresponding Rust type
+fn convex_type_to_rust_type(
+    data_type: &JsonValue,
+    table_name: Option<&str>,
+    field_name: Option<&str>,
+) -> Result<Type, ConvexTypeGeneratorError>
+{
+    // Pagination validators are expanded into objects by the parser, but map to the crate's types
+    match data_type["validator"].as_str() {
+        Some("paginationOptsValidator") => return Ok(parse_quote!(convex_typegen::pagination::PaginationOpts)),
+        Some("paginationResultValidator") => {
+            let item_type = convex_type_to_rust_type(&data_type["properties"]["page"]["elements"], None, None)?;
+            return Ok(parse_quote!(convex_typegen::pagination::PaginationResult<#item_type>));
+        }
+        _ => {}
+    }
+
+    // Get the base type from the "type" field
+    let type_str = data_type["type"].as_str().unwrap_or("unknown");
+
+    Ok(match type_str {
+        "string" => parse_quote!(String),
+        "number" => parse_quote!(f64),
+        "boolean" => parse_quote!(bool),
+        "null" => parse_quote!(()),
+        "int64" => parse_quote!(i64),
+        "bytes" => parse_quote!(Vec<u8>),
+        "any" => parse_quote!(serde_json::Value),
+
+        "array" => {
+            let element_type = convex_type_to_rust_type(&data_type["elements"], None, None)?;
+            parse_quote!(Vec<#element_type>)
+        }
+
+        "object" => {
+            if let Some(props) = data_type["properties"].as_object() {
+                let value_type = match props.values().next() {
+                    Some(value) => convex_type_to_rust_type(value, None, None)?,
+                    None => parse_quote!(serde_json::Value),
+                };
+                parse_quote!(std::collections::BTreeMap<String, #value_type>)
+            } else {
+                parse_quote!(serde_json::Value)
+            }
+        }
+
+        "record" => {
+            let key_type = convex_type_to_rust_type(&data_type["keyType"], None, None)?;
+            let value_type = convex_type_to_rust_type(&data_type["valueType"], None, None)?;
+            parse_quote!(std::collections::HashMap<#key_type, #value_type>)
+        }
+
+        "optional" => {
+            let inner_type: Type = match data_type["inner"]["type"].as_str() {
+                Some("union") => {
+                    if let (Some(table), Some(field)) = (table_name, field_name) {
+                        let enum_name = ident(&format!(
+                            "{}Optional{}",
+                            capitalize_first_letter(table),
+                            capitalize_first_letter(field)
+                        ))?;
+                        parse_quote!(#enum_name)
+                    } else {
+                        parse_quote!(serde_json::Value)
+                    }
+                }
+                _ => convex_type_to_rust_type(&data_type["inner"], None, None)?,
+            };
+            parse_quote!(Option<#inner_type>)
+        }
+
+        // Rust has no literal types, so a literal is typed by its value
+        "literal" => match literal_value(data_type) {
+            Some(ConvexValue::Int64(_)) => parse_quote!(i64),
+            Some(ConvexValue::Float64(_)) => parse_quote!(f64),
+            Some(ConvexValue::Boolean(_)) => parse_quote!(bool),
+            _ => parse_quote!(String),
+        },
+
+        "id" => parse_quote!(String),
+
+        _ => parse_quote!(serde_json::Value), // fallback for unknown types
+    })
+}
+
+/// Get the serde attribute for a field, if its Convex type needs one.
+///
+/// Optional fields are skipped when `None`, since Convex rejects `null` for `v.optional(...)`.
+/// `int64` and `bytes` fields need Convex's special JSON encoding.
+fn serde_field_attribute(data_type: &JsonValue) -> Option<Attribute>
+{
+    Some(match data_type["type"].as_str() {
+        Some("int64") => parse_quote!(#[serde(with = "convex_typegen::json::int64")]),
+        Some("bytes") => parse_quote!(#[serde(with = "convex_typegen::json::bytes")]),
+        Some("optional") => match data_type["inner"]["type"].as_str() {
+            Some("int64") => parse_quote! {
+                #[serde(default, skip_serializing_if = "Option::is_none", with = "convex_typegen::json::int64::option")]
+            },
+            Some("bytes") => parse_quote! {
+                #[serde(default, skip_serializing_if = "Option::is_none", with = "convex_typegen::json::bytes::option")]
+            },
+            _ => parse_quote!(#[serde(default, skip_serializing_if = "Option::is_none")]),
+        },
+        _ => return None,
+    })
+}
+
+/// Generate the `validator()` and `validate()` methods for a struct with the given fields.
+///
+/// `owner` names the table or function the fields belong to in errors.
+///
+/// # Errors
+/// Returns an error naming the field if its type can't be represented by a runtime validator, as
+/// the generated `validate()` would otherwise let invalid data through.
+fn generate_validator_methods<'a>(
+    owner: &str,
+    fields: impl Iterator<Item = (&'a str, &'a JsonValue)>,
+) -> Result<TokenStream, ConvexTypeGeneratorError>
+{
+    let fields = fields
+        .map(|(name, data_type)| {
+            let validator = validator_expr(data_type).map_err(|error| ConvexTypeGeneratorError::InvalidSchema {
+                context: format!("{}.{}", owner, name),
+                details: format!("field has no runtime validator: {}", error),
+            })?;
+            Ok(quote!((#name, #validator)))
+        })
+        .collect::<Result<Vec<_>, ConvexTypeGeneratorError>>()?;
+
+    Ok(quote! {
+        pub fn validator() -> convex_typegen::validator::Validator {
+            use convex_typegen::validator::v;
+            v::object([#(#fields),*])
+        }
+
+        pub fn validate(&self) -> Result<(), convex_typegen::errors::ConvexValidationError> {
+            Self::validator().validate_serialized(self)
+        }
+    })
+}
+
+/// Convert a data type into the Rust expression that builds its validator with
+/// `convex_typegen::validator::v`
+///
+/// # Errors
+/// Returns an error if the data type is not a known Convex type or is missing its inner types.
+fn validator_expr(data_type: &JsonValue) -> Result<TokenStream, ConvexTypeGeneratorError>
+{
+    let type_name = data_type["type"]
+        .as_str()
+        .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+            context: "validator".to_string(),
+            details: "Missing type name".to_string(),
+        })?;
+    validate_type_name(type_name)?;
+
+    let invalid = |details: &str| ConvexTypeGeneratorError::InvalidSchema {
+        context: format!("validator.{}", type_name),
+        details: details.to_string(),
+    };
+    // Literals are built from their magnitude, as negative literal tokens don't survive every parser
+    let minus = |negative: bool| negative.then(|| quote!(-));
+
+    Ok(match type_name {
+        "id" => {
+            let table = id_table_name(data_type).ok_or_else(|| invalid("Missing table name"))?;
+            quote!(v::id(#table))
+        }
+        "null" => quote!(v::null()),
+        "int64" => quote!(v::int64()),
+        "number" => quote!(v::number()),
+        "boolean" => quote!(v::boolean()),
+        "string" => quote!(v::string()),
+        "bytes" => quote!(v::bytes()),
+        "any" => quote!(v::any()),
+        "literal" => match literal_value(data_type).ok_or_else(|| invalid("Unsupported literal value"))? {
+            ConvexValue::String(s) => quote!(v::literal(#s)),
+            ConvexValue::Int64(i) => {
+                let (sign, value) = (
+                    minus(i < 0),
+                    LitInt::new(&format!("{}i64", i.unsigned_abs()), Span::call_site()),
+                );
+                quote!(v::literal(#sign #value))
+            }
+            ConvexValue::Float64(f) => {
+                let value = LitFloat::new(&format!("{:?}", f.abs()), Span::call_site());
+                let sign = minus(f.is_sign_negative());
+                quote!(v::literal(#sign #value))
+            }
+            ConvexValue::Boolean(b) => quote!(v::literal(#b)),
+            _ => return Err(invalid("Unsupported literal value")),
+        },
+        "array" => {
+            let elements = validator_expr(&data_type["elements"])?;
+            quote!(v::array(#elements))
+        }
+        "optional" => {
+            let inner = validator_expr(&data_type["inner"])?;
+            quote!(v::optional(#inner))
+        }
+        "record" => {
+            let key = validator_expr(&data_type["keyType"])?;
+            let value = validator_expr(&data_type["valueType"])?;
+            quote!(v::record(#key, #value))
+        }
+        "union" => {
+            let variants = data_type["variants"]
+                .as_array()
+                .ok_or_else(|| invalid("Missing union variants"))?
+                .iter()
+                .map(validator_expr)
+                .collect::<Result<Vec<_>, _>>()?;
+            quote!(v::union([#(#variants),*]))
+        }
+        "object" => {
+            let fields = data_type["properties"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(name, field)| {
+                    let validator = validator_expr(field)?;
+                    Ok(quote!((#name, #validator)))
+                })
+                .collect::<Result<Vec<_>, ConvexTypeGeneratorError>>()?;
+            quote!(v::object([#(#fields),*]))
+        }
+        _ => unreachable!("type name was validated"),
+    })
+}
+
+/// Generate the code for a function.
+fn generate_function_code(function: &ConvexFunction, type_name: &str) -> Result<Vec<Item>, ConvexTypeGeneratorError>
+{
+    let mut items = Vec::new();
+
+    // Generate the args struct name
+    let struct_name = ident(&format!("{}Args", type_name))?;
+    let path = lit_str(&format!("{}:{}", function.file_name, function.name));
+
+    // Generate fields for each parameter
+    let fields = function
+        .params
+        .iter()
+        .map(|param| {
+            let rust_type = convex_type_to_rust_type(&param.data_type, None, None)?;
+            struct_field(&param.name, &param.data_type, rust_type)
+        })
+        .collect::<Result<Vec<_>, ConvexTypeGeneratorError>>()?;
+    items.push(parse_quote! {
+        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+        pub struct #struct_name {
+            #(#fields,)*
+        }
+    });
+
+    // Add implementation block with static FUNCTION_PATH method
+    let validator_methods = generate_validator_methods(
+        &format!("{}:{}", function.file_name, function.name),
+        function.params.iter().map(|param| (param.name.as_str(), &param.data_type)),
+    )?;
+    items.push(parse_quote! {
+        impl #struct_name {
+            pub const FUNCTION_PATH: &'static str = #path;
+
+            #validator_methods
+        }
+    });
+
+    // Serialize the whole struct so field-level serde attributes are respected
+    items.push(parse_quote! {
+        impl TryFrom<#struct_name> for std::collections::BTreeMap<String, serde_json::Value> {
+            type Error = convex_typegen::errors::ConvexJsonError;
+
+            fn try_from(args: #struct_name) -> Result<Self, Self::Error> {
+                convex_typegen::json::to_args_map(args)
+            }
+        }
+    });
+
+    // Generate the return type and tie it to the args through the client trait
+    let return_name = format!("{}Return", type_name);
+    let pagination_param = function
+        .params
+        .iter()
+        .find(|param| param.data_type["validator"].as_str() == Some("paginationOptsValidator"));
+    items.extend(generate_return_type(
+        &return_name,
+        function.returns.as_ref(),
+        pagination_param.is_some(),
+    )?);
+
+    // Generate the type of the data thrown with `ConvexError`
+    let error_name = format!("{}Error", type_name);
+    items.push(generate_error_type(&error_name, &function.errors)?);
+
+    if let Some(kind) = function_kind(&function.type_) {
+        let kind = format_ident!("{}", kind);
+        let return_name = ident(&return_name)?;
+        let error_name = ident(&error_name)?;
+        let renamed = function
+            .params
+            .iter()
+            .map(|param| (param.name.as_str(), rust_ident(&param.name)))
+            .filter(|(name, field)| name != field)
+            .map(|(name, field)| quote!((#name, #field)))
+            .collect::<Vec<_>>();
+        let field_names = (!renamed.is_empty()).then(|| {
+            quote! {
+                const FIELD_NAMES: &'static [(&'static str, &'static str)] = &[#(#renamed),*];
+            }
+        });
+        items.push(parse_quote! {
+            impl convex_typegen::client::ConvexFunctionArgs for #struct_name {
+                const PATH: &'static str = #struct_name::FUNCTION_PATH;
+                type Kind = convex_typegen::client::#kind;
+                type Output = #return_name;
+                #field_names
+            }
+        });
+        items.push(parse_quote! {
+            impl convex_typegen::client::ConvexFunctionErrors for #struct_name {
+                type Error = #error_name;
+            }
+        });
+    }
+
+    // Paginated queries can be loaded page by page with `ConvexClientExt::paginate`
+    if let (Some(param), Some("Query")) = (pagination_param, function_kind(&function.type_)) {
+        let field = ident(&rust_ident(&param.name))?;
+        items.push(parse_quote! {
+            impl convex_typegen::pagination::PaginatedQuery for #struct_name {
+                fn pagination_opts(&mut self) -> &mut convex_typegen::pagination::PaginationOpts {
+                    &mut self.#field
+                }
+            }
+        });
+    }
+
+    Ok(items)
+}
+
+/// Generate the type a function returns.
+///
+/// Objects become structs, `v.union(v.object(...), v.null())` becomes an optional struct and other
+/// validators become type aliases. Functions without a `returns` validator return `serde_json::Value`,
+/// or a page of them if they take pagination options.
+fn generate_return_type(
+    return_name: &str,
+    returns: Option<&JsonValue>,
+    paginated: bool,
+) -> Result<Vec<Item>, ConvexTypeGeneratorError>
+{
+    let Some(returns) = returns else {
+        let return_name = ident(return_name)?;
+        let return_type: Type = if paginated {
+            parse_quote!(convex_typegen::pagination::PaginationResult<serde_json::Value>)
+        } else {
+            parse_quote!(serde_json::Value)
+        };
+        return Ok(vec![parse_quote!(pub type #return_name = #return_type;)]);
+    };
+
+    // Pages of objects get a struct for their items
+    if returns["validator"].as_str() == Some("paginationResultValidator") {
+        let item = &returns["properties"]["page"]["elements"];
+        if item["type"].as_str() == Some("object") {
+            let struct_name = format!("{}Item", return_name);
+            let object = generate_return_struct(&struct_name, item)?;
+            let (return_name, struct_name) = (ident(return_name)?, ident(&struct_name)?);
+            return Ok(vec![
+                object,
+                parse_quote!(pub type #return_name = convex_typegen::pagination::PaginationResult<#struct_name>;),
+            ]);
+        }
+    }
+
+    if returns["type"].as_str() == Some("object") && returns["validator"].is_null() {
+        return Ok(vec![generate_return_struct(return_name, returns)?]);
+    }
+
+    // A nullable object is the usual shape of a "get by id" query
+    if returns["type"].as_str() == Some("union") {
+        let variants = returns["variants"].as_array().map(Vec::as_slice).unwrap_or_default();
+        if let [first, second] = variants {
+            let object = match (first["type"].as_str(), second["type"].as_str()) {
+                (Some("object"), Some("null")) => Some(first),
+                (Some("null"), Some("object")) => Some(second),
+                _ => None,
+            };
+            if let Some(object) = object {
+                let struct_name = format!("{}Object", return_name);
+                let object = generate_return_struct(&struct_name, object)?;
+                let (return_name, struct_name) = (ident(return_name)?, ident(&struct_name)?);
+                return Ok(vec![object, parse_quote!(pub type #return_name = Option<#struct_name>;)]);
+            }
+        }
+    }
+
+    let return_name = ident(return_name)?;
+    let return_type = convex_type_to_rust_type(returns, None, None)?;
+    Ok(vec![parse_quote!(pub type #return_name = #return_type;)])
+}
+
+/// Fields whose string value names the kind of a thrown error, in order of preference
+const ERROR_TAGS: &[&str] = &["code", "kind", "type"];
+
+/// Generate the type of the data a function throws with `ConvexError`.
+///
+/// Object payloads that share a string literal `code` (or `kind` or `type`) field become the variants
+/// of an enum tagged by that field. Fields missing from some payloads of a variant are optional.
+/// String payloads are matched by a `Message` variant, and any other data by `Other`, so decoding
+/// never fails on errors thrown by code the generator could not see. Functions that throw nothing
+/// recognizable get `serde_json::Value`.
+fn generate_error_type(error_name: &str, errors: &[JsonValue]) -> Result<Item, ConvexTypeGeneratorError>
+{
+    let objects = errors
+        .iter()
+        .filter(|error| error["type"].as_str() == Some("object"))
+        .collect::<Vec<_>>();
+    let tag_value = |object: &JsonValue, tag: &str| {
+        let field = &object["properties"][tag];
+        match field["type"].as_str() {
+            Some("literal") => literal_value(field).and_then(|value| match value {
+                ConvexValue::String(value) => Some(value),
+                _ => None,
+            }),
+            _ => None,
+        }
+    };
+    let tag = ERROR_TAGS
+        .iter()
+        .copied()
+        .find(|tag| objects.iter().any(|object| tag_value(object, tag).is_some()));
+    let has_message = errors
+        .iter()
+        .any(|error| matches!(error["type"].as_str(), Some("literal") | Some("string")));
+
+    let error_name = ident(error_name)?;
+    if tag.is_none() && !has_message {
+        return Ok(parse_quote!(pub type #error_name = serde_json::Value;));
+    }
+
+    // Group the payloads by their tag, keeping the order they were thrown in
+    let mut variants: Vec<(String, Vec<&serde_json::Map<String, JsonValue>>)> = Vec::new();
+    if let Some(tag) = tag {
+        for object in &objects {
+            let (Some(value), Some(properties)) = (tag_value(object, tag), object["properties"].as_object()) else {
+                continue;
+            };
+            match variants.iter_mut().find(|(existing, _)| *existing == value) {
+                Some((_, shapes)) => shapes.push(properties),
+                None => variants.push((value, vec![properties])),
+            }
+        }
+    }
+
+    let mut variant_names = vec!["Message".to_string(), "Other".to_string()];
+    let mut tagged_variants = Vec::new();
+    for (value, shapes) in &variants {
+        let mut variant_name = to_pascal_case(value);
+        if variant_name.is_empty() || variant_name.starts_with(|c: char| c.is_ascii_digit()) {
+            variant_name.insert_str(0, "Code");
+        }
+        while variant_names.contains(&variant_name) {
+            variant_name.push('_');
+        }
+        variant_names.push(variant_name.clone());
+        let variant_name = ident(&variant_name)?;
+
+        // Every field seen in a payload of this kind, except the tag
+        let mut fields: Vec<(&String, &JsonValue, bool)> = Vec::new();
+        for shape in shapes {
+            for (name, data_type) in shape.iter().filter(|(name, _)| Some(name.as_str()) != tag) {
+                if !fields.iter().any(|(existing, _, _)| *existing == name) {
+                    let always_present = shapes.iter().all(|shape| shape.contains_key(name));
+                    fields.push((name, data_type, always_present));
+                }
+            }
+        }
+
+        if fields.is_empty() {
+            tagged_variants.push(quote! {
+                #[serde(rename = #value)]
+                #variant_name
+            });
+            continue;
+        }
+
+        let fields = fields
+            .into_iter()
+            .map(|(name, data_type, always_present)| {
+                let data_type = match data_type["type"].as_str() {
+                    // The literal values thrown are examples, not the only possible values
+                    Some("literal") => match literal_value(data_type) {
+                        Some(ConvexValue::String(_)) => json!({ "type": "string" }),
+                        Some(ConvexValue::Int64(_)) => json!({ "type": "int64" }),
+                        Some(ConvexValue::Boolean(_)) => json!({ "type": "boolean" }),
+                        _ => json!({ "type": "number" }),
+                    },
+                    _ => data_type.clone(),
+                };
+                let data_type = if always_present || data_type["type"].as_str() == Some("optional") {
+                    data_type
+                } else {
+                    json!({ "type": "optional", "inner": data_type })
+                };
+
+                let field = rust_ident(name);
+                let rename = (field.trim_start_matches("r#") != name).then(|| quote!(#[serde(rename = #name)]));
+                let field = ident(&field)?;
+                let attribute = serde_field_attribute(&data_type);
+                let rust_type = convex_type_to_rust_type(&data_type, None, None)?;
+                Ok(quote! {
+                    #rename
+                    #attribute
+                    #field: #rust_type
+                })
+            })
+            .collect::<Result<Vec<_>, ConvexTypeGeneratorError>>()?;
+        tagged_variants.push(quote! {
+            #[serde(rename = #value)]
+            #variant_name {
+                #(#fields,)*
+            }
+        });
+    }
+
+    let tag = tag.map(|tag| quote!(#[serde(tag = #tag)]));
+    let message = has_message.then(|| {
+        quote! {
+            #[serde(untagged)]
+            Message(String),
+        }
+    });
+    Ok(parse_quote! {
+        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+        #tag
+        pub enum #error_name {
+            #(#tagged_variants,)*
+            #message
+            #[serde(untagged)]
+            Other(serde_json::Value),
+        }
+    })
+}
+
+/// Generate a struct for an object returned by a function
+fn generate_return_struct(struct_name: &str, object: &JsonValue) -> Result<Item, ConvexTypeGeneratorError>
+{
+    let struct_name = ident(struct_name)?;
+    let fields = object["properties"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, data_type)| struct_field(name, data_type, convex_type_to_rust_type(data_type, None, None)?))
+        .collect::<Result<Vec<_>, ConvexTypeGeneratorError>>()?;
+
+    Ok(parse_quote! {
+        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+        pub struct #struct_name {
+            #(#fields,)*
+        }
+    })
+}
+
+/// Get the base name of each function's generated types, e.g. `SendMessage` for `SendMessageArgs`.
+///
+/// Functions exported under the same name from several modules are prefixed with their module path,
+/// e.g. `MessagesSend` and `AdminUsersSend`.
+fn function_type_names(functions: &ConvexFunctions) -> Vec<String>
+{
+    functions
+        .iter()
+        .map(|function| {
+            let collides = functions
+                .iter()
+                .any(|other| other.name == function.name && other.file_name != function.file_name);
+            if collides {
+                let prefix = function
+                    .file_name
+                    .split(|c: char| !c.is_alphanumeric())
+                    .filter(|segment| !segment.is_empty())
+                    .map(capitalize_first_letter)
+                    .collect::<String>();
+                format!("{}{}", prefix, capitalize_first_letter(&function.name))
+            } else {
+                capitalize_first_letter(&function.name)
+            }
+        })
+        .collect()
+}
+
+/// A module in the generated `api`/`internal` tree
+#[derive(Default)]
+struct ReferenceModule<'a>
+{
+    /// The functions of this module, with the base name of their generated types
+    functions: Vec<(&'a ConvexFunction, &'a str)>,
+    children: std::collections::BTreeMap<String, ReferenceModule<'a>>,
+}
+
+/// Generate `api` and `internal` modules that mirror the Convex module layout.
+///
+/// `api::messages::send` refers to the public function `send` in `convex/messages.ts`, and
+/// `internal::admin::users::purge` to the internal function `purge` in `convex/admin/users.ts`.
+fn generate_function_references(
+    functions: &ConvexFunctions,
+    type_names: &[String],
+) -> Result<Vec<Item>, ConvexTypeGeneratorError>
+{
+    let mut api = ReferenceModule::default();
+    let mut internal = ReferenceModule::default();
+
+    for (function, type_name) in functions.iter().zip(type_names) {
+        if function_kind(&function.type_).is_none() {
+            continue;
+        }
+
+        let root = if function.type_.starts_with("internal") {
+            &mut internal
+        } else {
+            &mut api
+        };
+        let module = function.file_name.split('/').fold(root, |module, segment| {
+            module.children.entry(rust_ident(segment)).or_default()
+        });
+
+        module.functions.push((function, type_name));
+    }
+
+    let mut items = Vec::new();
+    for (name, module) in [("api", api), ("internal", internal)] {
+        if module.functions.is_empty() && module.children.is_empty() {
+            continue;
+        }
+        let name = format_ident!("{}", name);
+        let content = generate_reference_module(&module, 1)?;
+        items.push(parse_quote! {
+            #[allow(non_upper_case_globals)]
+            pub mod #name {
+                #(#content)*
+            }
+        });
+    }
+
+    Ok(items)
+}
+
+fn generate_reference_module(module: &ReferenceModule<'_>, depth: usize) -> Result<Vec<Item>, ConvexTypeGeneratorError>
+{
+    let supers = (0..depth).map(|_| quote!(super::)).collect::<TokenStream>();
+    let mut items = Vec::new();
+
+    if !module.functions.is_empty() {
+        items.push(parse_quote!(
+            use convex_typegen::client::FunctionReference;
+        ));
+    }
+    for (function, type_name) in &module.functions {
+        let kind = format_ident!("{}", function_kind(&function.type_).unwrap_or_default());
+        let name = ident(&rust_ident(&function.name))?;
+        let args = ident(&format!("{}Args", type_name))?;
+        let output = ident(&format!("{}Return", type_name))?;
+        let path = lit_str(&format!("{}:{}", function.file_name, function.name));
+        items.push(parse_quote! {
+            pub const #name: FunctionReference<#supers #args, #supers #output, convex_typegen::client::#kind> =
+                FunctionReference::new(#path);
+        });
+    }
+
+    for (name, child) in &module.children {
+        let name = ident(name)?;
+        let content = generate_reference_module(child, depth + 1)?;
+        items.push(parse_quote! {
+            pub mod #name {
+                #(#content)*
+            }
+        });
+    }
+
+    Ok(items)
+}
+
+/// Generate the `FUNCTIONS` registry describing every query, mutation and action.
+fn generate_function_registry(functions: &ConvexFunctions) -> Vec<Item>
+{
+    let entries = functions.iter().filter_map(|function| {
+        let kind = format_ident!("{}", function_kind(&function.type_)?);
+        let visibility = if function.type_.starts_with("internal") {
+            format_ident!("Internal")
+        } else {
+            format_ident!("Public")
+        };
+        let runtime = if function.use_node {
+            format_ident!("Node")
+        } else {
+            format_ident!("Convex")
+        };
+        let path = lit_str(&format!("{}:{}", function.file_name, function.name));
+        let args = function.params.iter().map(|param| {
+            let (name, data_type) = (&param.name, describe_data_type(&param.data_type));
+            quote!((#name, #data_type))
+        });
+        let returns = match &function.returns {
+            Some(returns) => {
+                let returns = describe_data_type(returns);
+                quote!(Some(#returns))
+            }
+            None => quote!(None),
+        };
+
+        Some(quote! {
+            convex_typegen::registry::FunctionInfo {
+                path: #path,
+                function_type: convex_typegen::registry::FunctionType::#kind,
+                visibility: convex_typegen::registry::Visibility::#visibility,
+                runtime: convex_typegen::registry::Runtime::#runtime,
+                args: &[#(#args),*],
+                returns: #returns,
+            }
+        })
+    });
+
+    vec![
+        parse_quote! {
+            pub static FUNCTIONS: &[convex_typegen::registry::FunctionInfo] = &[#(#entries),*];
+        },
+        parse_quote! {
+            /// A mock deployment that only accepts calls to the public functions above.
+            pub fn mock_transport() -> convex_typegen::mock::MockTransport {
+                convex_typegen::mock::MockTransport::for_functions(FUNCTIONS)
+            }
+        },
+    ]
+}
+
+/// Generate the `ConvexCall` enum with one variant per public function.
+///
+/// Calls serialize as `{"path": "messages:send", "args": {...}}`, so they can be stored in queues and
+/// audit logs and dispatched later. Internal functions can't be called by clients and are left out.
+fn generate_call_enum(functions: &ConvexFunctions, type_names: &[String]) -> Result<Vec<Item>, ConvexTypeGeneratorError>
+{
+    let mut paths = Vec::new();
+    let mut variants = Vec::new();
+    let mut args = Vec::new();
+    for (function, type_name) in functions.iter().zip(type_names) {
+        if function_kind(&function.type_).is_none() || function.type_.starts_with("internal") {
+            continue;
+        }
+        paths.push(lit_str(&format!("{}:{}", function.file_name, function.name)));
+        variants.push(ident(type_name)?);
+        args.push(ident(&format!("{}Args", type_name))?);
+    }
+
+    if variants.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut items: Vec<Item> = vec![
+        parse_quote! {
+            #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+            #[serde(tag = "path", content = "args")]
+            pub enum ConvexCall {
+                #(
+                    #[serde(rename = #paths)]
+                    #variants(#args),
+                )*
+            }
+        },
+        parse_quote! {
+            impl ConvexCall {
+                pub fn path(&self) -> &'static str {
+                    match self {
+                        #(Self::#variants(_) => #args::FUNCTION_PATH,)*
+                    }
+                }
+
+                pub async fn dispatch<C>(self, client: &mut C) -> Result<convex::Value, convex_typegen::errors::ConvexCallError>
+                where
+                    C: convex_typegen::client::ConvexTransport + Send,
+                {
+                    use convex_typegen::convex::ConvexClientExt;
+                    match self {
+                        #(Self::#variants(args) => client.call_function(args).await,)*
+                    }
+                }
+            }
+        },
+        parse_quote! {
+            impl convex_typegen::client::ConvexFunctionCall for ConvexCall {
+                fn path(&self) -> &'static str {
+                    ConvexCall::path(self)
+                }
+
+                fn dispatch<T>(
+                    self,
+                    transport: &mut T,
+                ) -> impl std::future::Future<Output = Result<convex::Value, convex_typegen::errors::ConvexCallError>> + Send
+                where
+                    T: convex_typegen::client::ConvexTransport + Send,
+                {
+                    ConvexCall::dispatch(self, transport)
+                }
+            }
+        },
+    ];
+
+    for (variant, args) in variants.iter().zip(&args) {
+        items.push(parse_quote! {
+            impl From<#args> for ConvexCall {
+                fn from(args: #args) -> Self {
+                    Self::#variant(args)
+                }
+            }
+        });
+    }
+
+    Ok(items)
+}
+
+/// Turn a Convex module or function name into a valid Rust identifier
+fn rust_ident(name: &str) -> String
+{
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn", "else", "enum",
+        "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move",
+        "mut", "override", "priv", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type", "typeof",
+        "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+
+    let mut ident = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
+        .collect::<String>();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+
+    match ident.as_str() {
+        // These can't be raw identifiers
+        "crate" | "self" | "Self" | "super" => format!("{}_", ident),
+        _ if KEYWORDS.contains(&ident.as_str()) => format!("r#{}", ident),
+        _ => ident,
+    }
+}
+
+fn capitalize_first_letter(s: &str) -> String
+{
+    s.chars().next().unwrap().to_uppercase().collect::<String>() + &s[1..]
+}
+
+fn to_pascal_case(s: &str) -> String
+{
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().collect::<String>() + &chars.collect::<String>().to_lowercase(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,1505 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+use convex::Value as ConvexValue;
+use oxc::allocator::Allocator;
+use oxc::diagnostics::OxcDiagnostic;
+use oxc::parser::Parser;
+use oxc::semantic::SemanticBuilder;
+use oxc::span::SourceType;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+
+use crate::errors::ConvexTypeGeneratorError;
+use crate::Configuration;
+
+/// The convex schema.
+///
+/// A schema can contain many tables. https://docs.convex.dev/database/schemas
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConvexSchema
+{
+    /// The tables defined in the schema.
+    pub tables: Vec<ConvexTable>,
+}
+
+/// A table in the convex schema.
+///
+/// A table can contain many columns.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConvexTable
+{
+    /// The name of the table.
+    pub name: String,
+    /// The columns in the table.
+    pub columns: Vec<ConvexColumn>,
+    /// The indexes defined on the table with `.index(...)`, in declaration order.
+    #[serde(default)]
+    pub indexes: Vec<ConvexIndex>,
+}
+
+/// A column in the convex schema.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConvexColumn
+{
+    /// The name of the column.
+    pub name: String,
+    /// The data type of the column.
+    /// https://docs.rs/convex/latest/convex/enum.Value.html
+    pub data_type: JsonValue,
+}
+
+/// An index on a convex table.
+///
+/// https://docs.convex.dev/database/reading-data/indexes
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConvexIndex
+{
+    /// The name of the index, e.g. `by_author`.
+    pub name: String,
+    /// The indexed fields, in order. Nested fields use dotted paths like `author.name`.
+    pub fields: Vec<String>,
+}
+
+/// A collection of all convex functions.
+pub type ConvexFunctions = Vec<ConvexFunction>;
+
+/// Convex functions (Queries, Mutations, and Actions)
+///
+/// https://docs.convex.dev/functions
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConvexFunction
+{
+    /// The exported name of the function.
+    pub name: String,
+    /// The arguments declared in the function's `args` object.
+    pub params: Vec<ConvexFunctionParam>,
+    /// The function constructor, e.g. `query`, `mutation` or `internalAction`.
+    pub type_: String,
+    /// The module the function is exported from, relative to the Convex functions directory and
+    /// without the extension, e.g. `messages` or `admin/users`.
+    pub file_name: String,
+    /// The data type declared in the function's `returns` validator, if any.
+    #[serde(default)]
+    pub returns: Option<JsonValue>,
+    /// Whether the module starts with a `"use node"` directive, making the function run in Node.js.
+    #[serde(default)]
+    pub use_node: bool,
+    /// The data types of the `ConvexError` payloads thrown by the handler, or by functions of the
+    /// same module it calls, e.g. `v.object({ code: v.literal("NOT_FOUND"), id: v.id("posts") })`.
+    #[serde(default)]
+    pub errors: Vec<JsonValue>,
+}
+
+/// A parameter in a convex function.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConvexFunctionParam
+{
+    /// The name of the parameter.
+    pub name: String,
+    /// The data type of the parameter, in the same format as [`ConvexColumn::data_type`].
+    pub data_type: JsonValue,
+}
+
+/// Creates an AST from a schema file.
+///
+/// # Arguments
+/// * `path` - Path to the schema file
+///
+/// # Errors
+/// Returns an error if:
+/// * The file cannot be read
+/// * The file contains invalid syntax
+/// * The AST cannot be generated
+pub(crate) fn create_schema_ast(path: PathBuf, cache: &mut AstCache) -> Result<JsonValue, ConvexTypeGeneratorError>
+{
+    // Validate path exists before processing
+    if !cache.contains(&path) {
+        return Err(ConvexTypeGeneratorError::MissingSchemaFile);
+    }
+
+    cache.ast(&path)
+}
+
+/// Creates a map of all convex functions from a list of function paths.
+///
+/// The map is keyed by module path, e.g. `messages` or `admin/users`, relative to `functions_dir`.
+pub(crate) fn create_functions_ast(
+    paths: Vec<PathBuf>,
+    functions_dir: &Path,
+    cache: &mut AstCache,
+) -> Result<BTreeMap<String, JsonValue>, ConvexTypeGeneratorError>
+{
+    let mut functions = BTreeMap::new();
+
+    for path in paths {
+        let function_ast = cache.ast(&path)?;
+        functions.insert(module_path(&path, functions_dir)?, function_ast);
+    }
+
+    Ok(functions)
+}
+
+/// Convex identifies a module by its path relative to the functions directory, without the extension.
+///
+/// # Errors
+/// Returns an error if the file is outside of `functions_dir`, which must be absolute.
+fn module_path(path: &Path, functions_dir: &Path) -> Result<String, ConvexTypeGeneratorError>
+{
+    let path_str = path.to_string_lossy().to_string();
+    let absolute = absolute_path(path)?;
+
+    let relative = absolute.strip_prefix(functions_dir).map_err(|_| {
+        ConvexTypeGeneratorError::InvalidPath(format!(
+            "{} is outside of the functions directory {}",
+            path_str,
+            functions_dir.display()
+        ))
+    })?;
+
+    let segments = relative
+        .with_extension("")
+        .components()
+        .map(|component| {
+            component
+                .as_os_str()
+                .to_str()
+                .map(str::to_string)
+                .ok_or_else(|| ConvexTypeGeneratorError::InvalidUnicode(path_str.clone()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(segments.join("/"))
+}
+
+/// The deepest directory containing all of the given files, as an absolute path.
+///
+/// # Errors
+/// Returns an error if a path cannot be made absolute.
+pub(crate) fn common_directory(paths: &[PathBuf]) -> Result<PathBuf, ConvexTypeGeneratorError>
+{
+    let mut dirs = Vec::new();
+    for path in paths {
+        dirs.push(absolute_path(path)?.parent().map(Path::to_path_buf).unwrap_or_default());
+    }
+
+    let mut dirs = dirs.into_iter();
+    let Some(mut common) = dirs.next() else {
+        return Ok(PathBuf::new());
+    };
+
+    for dir in dirs {
+        while !dir.starts_with(&common) {
+            if !common.pop() {
+                break;
+            }
+        }
+    }
+
+    Ok(common)
+}
+
+/// The absolute form of `path`, with `.` and `..` resolved lexically.
+///
+/// Unlike `canonicalize` this doesn't touch the disk, so it works the same for files that only
+/// exist in memory.
+///
+/// # Errors
+/// Returns an error if the path is empty or the current directory cannot be read.
+pub(crate) fn absolute_path(path: &Path) -> Result<PathBuf, ConvexTypeGeneratorError>
+{
+    let absolute = std::path::absolute(path).map_err(|error| ConvexTypeGeneratorError::IOError {
+        file: path.to_string_lossy().to_string(),
+        error,
+    })?;
+
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    Ok(normalized)
+}
+
+pub(crate) fn parse_schema_ast(ast: JsonValue) -> Result<ConvexSchema, ConvexTypeGeneratorError>
+{
+    let context = "root";
+    // Get the body array
+    let body = ast["body"]
+        .as_array()
+        .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+            context: context.to_string(),
+            details: "Missing body array".to_string(),
+        })?;
+
+    // Find the defineSchema call
+    let define_schema = find_define_schema(body).ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+        context: context.to_string(),
+        details: "Could not find defineSchema call".to_string(),
+    })?;
+
+    // Get the arguments array of defineSchema
+    let schema_args = define_schema["arguments"]
+        .as_array()
+        .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+            context: context.to_string(),
+            details: "Missing schema arguments".to_string(),
+        })?;
+
+    // Get the first argument which is an object containing table definitions
+    let tables_obj = schema_args
+        .first()
+        .and_then(|arg| arg["properties"].as_array())
+        .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+            context: context.to_string(),
+            details: "Missing table definitions".to_string(),
+        })?;
+
+    let mut tables = Vec::new();
+
+    // Iterate through each table definition
+    for table_prop in tables_obj {
+        // Get the table name
+        let table_name = table_prop["key"]["name"]
+            .as_str()
+            .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+                context: context.to_string(),
+                details: "Invalid table name".to_string(),
+            })?;
+
+        // Indexes are chained onto defineTable, e.g. `defineTable({...}).index("by_author", ["author"])`
+        let mut define_table = &table_prop["value"];
+        let mut indexes = Vec::new();
+        while let Some(method) = define_table["callee"]["property"]["name"].as_str() {
+            // Search and vector indexes can't be read through the client, so they are skipped
+            if method == "index" {
+                indexes.push(parse_index(define_table, table_name)?);
+            }
+            define_table = &define_table["callee"]["object"];
+        }
+        indexes.reverse();
+
+        // Get the defineTable call arguments
+        let define_table_args =
+            define_table["arguments"]
+                .as_array()
+                .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+                    context: context.to_string(),
+                    details: "Invalid table definition".to_string(),
+                })?;
+
+        // Get the first argument which contains column definitions
+        let columns_obj = define_table_args
+            .first()
+            .and_then(|arg| arg["properties"].as_array())
+            .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+                context: context.to_string(),
+                details: "Missing column definitions".to_string(),
+            })?;
+
+        let mut columns = Vec::new();
+
+        // Iterate through each column definition
+        for column_prop in columns_obj {
+            // Get column name
+            let column_name =
+                column_prop["key"]["name"]
+                    .as_str()
+                    .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+                        context: context.to_string(),
+                        details: "Invalid column name".to_string(),
+                    })?;
+
+            // Get column type by looking at the property chain
+            let mut context = TypeContext::new(context.to_string());
+            let column_type = extract_column_type(column_prop, &mut context)?;
+
+            columns.push(ConvexColumn {
+                name: column_name.to_string(),
+                data_type: column_type,
+            });
+        }
+
+        tables.push(ConvexTable {
+            name: table_name.to_string(),
+            columns,
+            indexes,
+        });
+    }
+
+    Ok(ConvexSchema { tables })
+}
+
+/// Helper function to parse an `.index(name, fields)` call on a table definition
+fn parse_index(call: &JsonValue, table_name: &str) -> Result<ConvexIndex, ConvexTypeGeneratorError>
+{
+    let invalid = |details: &str| ConvexTypeGeneratorError::InvalidSchema {
+        context: format!("{}.index", table_name),
+        details: details.to_string(),
+    };
+
+    let name = call["arguments"][0]["value"]
+        .as_str()
+        .ok_or_else(|| invalid("Index name must be a string literal"))?;
+    let fields = call["arguments"][1]["elements"]
+        .as_array()
+        .ok_or_else(|| invalid("Index fields must be an array literal"))?
+        .iter()
+        .map(|field| {
+            field["value"]
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| invalid("Index fields must be string literals"))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(ConvexIndex {
+        name: name.to_string(),
+        fields,
+    })
+}
+
+/// Helper function to find the defineSchema call in the AST
+fn find_define_schema(body: &[JsonValue]) -> Option<&JsonValue>
+{
+    for node in body {
+        // Check if this is an export default declaration
+        if let Some(declaration) = node.get("declaration") {
+            // Check if this is a call expression
+            if declaration["type"].as_str() == Some("CallExpression") {
+                // Check if the callee is defineSchema
+                if let Some(callee) = declaration.get("callee") {
+                    if callee["type"].as_str() == Some("Identifier") && callee["name"].as_str() == Some("defineSchema") {
+                        return Some(declaration);
+                    }
+                }
+            }
+        }
+
+        // Could also be a regular variable declaration or expression
+        // that calls defineSchema
+        if node["type"].as_str() == Some("CallExpression") {
+            if let Some(callee) = node.get("callee") {
+                if callee["type"].as_str() == Some("Identifier") && callee["name"].as_str() == Some("defineSchema") {
+                    return Some(node);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Helper function to extract the column type from a column property
+fn extract_column_type(column_prop: &JsonValue, context: &mut TypeContext) -> Result<JsonValue, ConvexTypeGeneratorError>
+{
+    let value = &column_prop["value"];
+    let callee = &value["callee"];
+
+    // Convex's pagination validators are imported from `convex/server` rather than built with `v`
+    if let Some(pagination_type) = extract_pagination_type(value, context)? {
+        return Ok(pagination_type);
+    }
+
+    let type_name = callee["property"]["name"]
+        .as_str()
+        .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+            context: context.get_error_context(),
+            details: "Invalid column type".to_string(),
+        })?;
+
+    // Validate the type name
+    validate_type_name(type_name)?;
+
+    let binding = Vec::new();
+    let args = value["arguments"].as_array().unwrap_or(&binding);
+
+    let mut type_obj = serde_json::Map::new();
+    type_obj.insert("type".to_string(), JsonValue::String(type_name.to_string()));
+
+    // Handle nested types
+    match type_name {
+        "optional" => {
+            // For optional types, recursively parse the inner type
+            if let Some(inner_type) = args.first() {
+                let inner_type_prop = json!({
+                    "key": { "name": "inner" },
+                    "value": inner_type
+                });
+                context.type_path.push("inner".to_string());
+                let parsed_inner_type = extract_column_type(&inner_type_prop, context)?;
+                context.type_path.pop();
+                type_obj.insert("inner".to_string(), parsed_inner_type);
+            } else {
+                return Err(ConvexTypeGeneratorError::InvalidSchema {
+                    context: context.type_path.join("."),
+                    details: "Optional type must have an inner type".to_string(),
+                });
+            }
+        }
+        "array" => {
+            // For arrays, recursively parse the element type
+            if let Some(element_type) = args.first() {
+                let element_type_prop = json!({
+                    "key": { "name": "element" },
+                    "value": element_type
+                });
+                context.type_path.push("elements".to_string());
+                let parsed_element_type = extract_column_type(&element_type_prop, context)?;
+                context.type_path.pop();
+                type_obj.insert("elements".to_string(), parsed_element_type);
+            }
+        }
+        "object" => {
+            // For objects, parse each property type
+            if let Some(obj_def) = args.first() {
+                if let Some(properties) = obj_def["properties"].as_array() {
+                    let mut prop_types = serde_json::Map::new();
+
+                    for prop in properties {
+                        let prop_name =
+                            prop["key"]["name"]
+                                .as_str()
+                                .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+                                    context: context.type_path.join("."),
+                                    details: "Invalid object property name".to_string(),
+                                })?;
+
+                        let prop_type = extract_column_type(prop, context)?;
+                        prop_types.insert(prop_name.to_string(), prop_type);
+                    }
+
+                    type_obj.insert("properties".to_string(), JsonValue::Object(prop_types));
+                }
+            }
+        }
+        "record" => {
+            // For records, parse both key and value types
+            if args.len() >= 2 {
+                // First argument is the key type
+                let key_type_prop = json!({
+                    "key": { "name": "key" },
+                    "value": args[0]
+                });
+                let key_type = extract_column_type(&key_type_prop, context)?;
+                type_obj.insert("keyType".to_string(), key_type);
+
+                // Second argument is the value type
+                let value_type_prop = json!({
+                    "key": { "name": "value" },
+                    "value": args[1]
+                });
+                let value_type = extract_column_type(&value_type_prop, context)?;
+                type_obj.insert("valueType".to_string(), value_type);
+            }
+        }
+        "union" => {
+            // For unions, parse all variant types
+            let mut variants = Vec::new();
+            for variant in args {
+                let variant_prop = json!({
+                    "key": { "name": "variant" },
+                    "value": variant
+                });
+                let variant_type = extract_column_type(&variant_prop, context)?;
+                variants.push(variant_type);
+            }
+            type_obj.insert("variants".to_string(), JsonValue::Array(variants));
+        }
+        "literal" => {
+            // For literals, store the literal value
+            if let Some(literal_value) = args.first() {
+                type_obj.insert("value".to_string(), literal_value.clone());
+            }
+        }
+        // For other types, just include their arguments if any
+        _ => {
+            if !args.is_empty() {
+                type_obj.insert("arguments".to_string(), JsonValue::Array(args.to_vec()));
+            }
+        }
+    }
+
+    // Build the type object as before...
+    let type_value = JsonValue::Object(type_obj);
+
+    // Check for circular references
+    check_circular_references(&type_value, context)?;
+
+    Ok(type_value)
+}
+
+pub(crate) fn parse_function_ast(ast_map: BTreeMap<String, JsonValue>) -> Result<ConvexFunctions, ConvexTypeGeneratorError>
+{
+    let mut functions = Vec::new();
+
+    for (file_name, ast) in ast_map {
+        // Get the body array
+        let body = ast["body"]
+            .as_array()
+            .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+                context: format!("file_{}", file_name),
+                details: "Missing body array".to_string(),
+            })?;
+
+        // Modules starting with "use node" run in the Node.js runtime
+        let use_node = ast["directives"]
+            .as_array()
+            .is_some_and(|directives| directives.iter().any(|d| d["directive"].as_str() == Some("use node")));
+
+        // Functions of the module that handlers may call, and that may throw
+        let helpers = module_helpers(body);
+
+        for node in body {
+            // Look for export declarations
+            if node["type"].as_str() == Some("ExportNamedDeclaration") {
+                if let Some(declaration) = node.get("declaration") {
+                    // Handle variable declarations (const testQuery = query({...}))
+                    if declaration["type"].as_str() == Some("VariableDeclaration") {
+                        if let Some(declarators) = declaration["declarations"].as_array() {
+                            for declarator in declarators {
+                                // Get function name
+                                let name = declarator["id"]["name"].as_str().ok_or_else(|| {
+                                    ConvexTypeGeneratorError::InvalidSchema {
+                                        context: format!("file_{}", file_name),
+                                        details: "Missing function name".to_string(),
+                                    }
+                                })?;
+
+                                // Get the function call (query/mutation/action)
+                                let init = &declarator["init"];
+                                if init["type"].as_str() == Some("CallExpression") {
+                                    // Get the callee to determine function type
+                                    let fn_type = init["callee"]["name"].as_str().ok_or_else(|| {
+                                        ConvexTypeGeneratorError::InvalidSchema {
+                                            context: format!("function_{}", name),
+                                            details: "Missing function type".to_string(),
+                                        }
+                                    })?;
+
+                                    // Get the first argument which contains the function config
+                                    if let Some(args) = init["arguments"].as_array() {
+                                        if let Some(config) = args.first() {
+                                            // Extract function parameters from the args property
+                                            let params = extract_function_params(config, &file_name)?;
+                                            let returns = extract_function_returns(config, name)?;
+                                            let errors = extract_function_errors(config, &helpers, &params);
+
+                                            functions.push(ConvexFunction {
+                                                name: name.to_string(),
+                                                params,
+                                                type_: fn_type.to_string(),
+                                                file_name: file_name.to_string(),
+                                                returns,
+                                                use_node,
+                                                errors,
+                                            });
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(functions)
+}
+
+/// Helper function to extract function parameters from the function configuration
+fn extract_function_params(config: &JsonValue, file_name: &str)
+    -> Result<Vec<ConvexFunctionParam>, ConvexTypeGeneratorError>
+{
+    let mut params = Vec::new();
+
+    // Get the args object from the function config
+    if let Some(properties) = config["properties"].as_array() {
+        for prop in properties {
+            if prop["key"]["name"].as_str() == Some("args") {
+                // Ensure args is an object
+                if prop["value"]["type"].as_str() != Some("ObjectExpression") {
+                    return Err(ConvexTypeGeneratorError::InvalidSchema {
+                        context: format!("file_{}", file_name),
+                        details: "Function args must be an object".to_string(),
+                    });
+                }
+
+                // Get the args object value
+                if let Some(args_props) = prop["value"]["properties"].as_array() {
+                    for arg_prop in args_props {
+                        // Validate argument property structure
+                        if arg_prop["type"].as_str() != Some("ObjectProperty") {
+                            return Err(ConvexTypeGeneratorError::InvalidSchema {
+                                context: format!("file_{}", file_name),
+                                details: "Invalid argument property structure".to_string(),
+                            });
+                        }
+
+                        // Get parameter name
+                        let param_name =
+                            arg_prop["key"]["name"]
+                                .as_str()
+                                .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+                                    context: format!("file_{}", file_name),
+                                    details: "Invalid parameter name".to_string(),
+                                })?;
+
+                        // Get parameter type using the same extraction logic as schema
+                        let mut context = TypeContext::new(format!("function_{}", param_name));
+                        let param_type = extract_column_type(arg_prop, &mut context)?;
+
+                        params.push(ConvexFunctionParam {
+                            name: param_name.to_string(),
+                            data_type: param_type,
+                        });
+                    }
+                }
+                break; // Found args object, no need to continue
+            }
+        }
+    }
+
+    Ok(params)
+}
+
+/// Helper function to extract the `returns` validator from the function configuration
+fn extract_function_returns(config: &JsonValue, function_name: &str) -> Result<Option<JsonValue>, ConvexTypeGeneratorError>
+{
+    let Some(properties) = config["properties"].as_array() else {
+        return Ok(None);
+    };
+
+    match properties.iter().find(|prop| prop["key"]["name"].as_str() == Some("returns")) {
+        Some(prop) => {
+            let mut context = TypeContext::new(format!("function_{}_returns", function_name));
+            extract_column_type(prop, &mut context).map(Some)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Helper function to find the named functions declared at the top level of a module
+fn module_helpers(body: &[JsonValue]) -> BTreeMap<&str, &JsonValue>
+{
+    let mut helpers = BTreeMap::new();
+
+    for node in body {
+        let declaration = match node["type"].as_str() {
+            Some("ExportNamedDeclaration") | Some("ExportDefaultDeclaration") => &node["declaration"],
+            _ => node,
+        };
+
+        match declaration["type"].as_str() {
+            Some("FunctionDeclaration") => {
+                if let Some(name) = declaration["id"]["name"].as_str() {
+                    helpers.insert(name, declaration);
+                }
+            }
+            Some("VariableDeclaration") => {
+                for declarator in declaration["declarations"].as_array().into_iter().flatten() {
+                    let init = &declarator["init"];
+                    let is_function = matches!(
+                        init["type"].as_str(),
+                        Some("ArrowFunctionExpression") | Some("FunctionExpression")
+                    );
+                    if let (true, Some(name)) = (is_function, declarator["id"]["name"].as_str()) {
+                        helpers.insert(name, init);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    helpers
+}
+
+/// Helper function to collect the payloads of the `ConvexError`s a function's handler throws.
+///
+/// Calls to functions of the same module are followed, so errors thrown by shared helpers are
+/// included. Each payload is described with the same data types as arguments, as far as it can be
+/// inferred from the expression: literals, template strings and the handler's arguments, read as
+/// `args.id` or destructured as `{ id }`, have a known type, anything else is `v.any()`.
+fn extract_function_errors(
+    config: &JsonValue,
+    helpers: &BTreeMap<&str, &JsonValue>,
+    params: &[ConvexFunctionParam],
+) -> Vec<JsonValue>
+{
+    let Some(handler) = config["properties"]
+        .as_array()
+        .and_then(|properties| properties.iter().find(|prop| prop["key"]["name"].as_str() == Some("handler")))
+    else {
+        return Vec::new();
+    };
+
+    let handler = &handler["value"];
+    let scope = ArgScope::of_handler(handler, params);
+
+    let mut errors = Vec::new();
+    let mut visited = Vec::new();
+    collect_thrown_errors(&handler["body"], helpers, &mut visited, &scope, &mut errors);
+
+    errors
+}
+
+/// The names under which the code of a handler reads the handler's arguments
+#[derive(Debug, Clone, Default)]
+struct ArgScope
+{
+    /// The name of the args object, e.g. `args` in `(ctx, args) => ...`
+    object: Option<String>,
+    /// The type of each argument, by argument name
+    args: BTreeMap<String, JsonValue>,
+    /// The type of each destructured argument, by the local name it is bound to
+    bindings: BTreeMap<String, JsonValue>,
+}
+
+impl ArgScope
+{
+    /// The scope at the start of a handler's body, from its second parameter
+    fn of_handler(handler: &JsonValue, params: &[ConvexFunctionParam]) -> Self
+    {
+        let args = params
+            .iter()
+            .map(|param| (param.name.clone(), param.data_type.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let pattern = &handler["params"]["items"][1]["pattern"];
+
+        match pattern["type"].as_str() {
+            Some("Identifier") => Self {
+                object: pattern["name"].as_str().map(str::to_string),
+                args,
+                bindings: BTreeMap::new(),
+            },
+            Some("ObjectPattern") => {
+                let bindings = pattern["properties"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|prop| prop["type"].as_str() == Some("BindingProperty") && prop["computed"] != json!(true))
+                    .filter_map(|prop| {
+                        let data_type = args.get(prop["key"]["name"].as_str()?)?;
+                        let local = match prop["value"]["type"].as_str() {
+                            Some("AssignmentPattern") => &prop["value"]["left"],
+                            _ => &prop["value"],
+                        };
+                        match local["type"].as_str() {
+                            Some("Identifier") => Some((local["name"].as_str()?.to_string(), data_type.clone())),
+                            _ => None,
+                        }
+                    })
+                    .collect();
+                Self {
+                    object: None,
+                    args,
+                    bindings,
+                }
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// The scope inside a node that declares `names`, which shadow arguments of the same name
+    fn without(&self, names: &[&str]) -> Self
+    {
+        let mut scope = self.clone();
+        if scope.object.as_deref().is_some_and(|object| names.contains(&object)) {
+            scope.object = None;
+        }
+        scope.bindings.retain(|name, _| !names.contains(&name.as_str()));
+        scope
+    }
+
+    /// The type of an identifier bound to a destructured argument
+    fn binding(&self, name: Option<&str>) -> Option<JsonValue>
+    {
+        self.bindings.get(name?).cloned()
+    }
+
+    /// The type of `object.property`, if `object` is the args object
+    fn member(&self, object: Option<&str>, property: Option<&str>) -> Option<JsonValue>
+    {
+        match (&self.object, object) {
+            (Some(args), Some(object)) if args == object => self.args.get(property?).cloned(),
+            _ => None,
+        }
+    }
+}
+
+/// The names a node binds for its own subtree, e.g. the parameters of a function or the variables
+/// declared in a block
+fn declared_names(node: &JsonValue) -> Vec<&str>
+{
+    let mut names = Vec::new();
+
+    match node["type"].as_str() {
+        Some("ArrowFunctionExpression") | Some("FunctionExpression") | Some("FunctionDeclaration") => {
+            for param in node["params"]["items"].as_array().into_iter().flatten() {
+                pattern_names(&param["pattern"], &mut names);
+            }
+            pattern_names(&node["params"]["rest"]["argument"], &mut names);
+        }
+        Some("FunctionBody") | Some("BlockStatement") | Some("Program") => {
+            let statements = node.get("statements").unwrap_or(&node["body"]);
+            for statement in statements.as_array().into_iter().flatten() {
+                match statement["type"].as_str() {
+                    Some("VariableDeclaration") => declaration_names(statement, &mut names),
+                    Some("FunctionDeclaration") | Some("ClassDeclaration") => pattern_names(&statement["id"], &mut names),
+                    _ => {}
+                }
+            }
+        }
+        Some("ForStatement") => declaration_names(&node["init"], &mut names),
+        Some("ForInStatement") | Some("ForOfStatement") => declaration_names(&node["left"], &mut names),
+        Some("CatchClause") => pattern_names(&node["param"]["pattern"], &mut names),
+        _ => {}
+    }
+
+    names
+}
+
+fn declaration_names<'a>(declaration: &'a JsonValue, names: &mut Vec<&'a str>)
+{
+    if declaration["type"].as_str() == Some("VariableDeclaration") {
+        for declarator in declaration["declarations"].as_array().into_iter().flatten() {
+            pattern_names(&declarator["id"], names);
+        }
+    }
+}
+
+fn pattern_names<'a>(pattern: &'a JsonValue, names: &mut Vec<&'a str>)
+{
+    match pattern["type"].as_str() {
+        Some("Identifier") => names.extend(pattern["name"].as_str()),
+        Some("ObjectPattern") => {
+            for prop in pattern["properties"].as_array().into_iter().flatten() {
+                match prop["type"].as_str() {
+                    Some("RestElement") => pattern_names(&prop["argument"], names),
+                    _ => pattern_names(&prop["value"], names),
+                }
+            }
+            pattern_names(&pattern["rest"]["argument"], names);
+        }
+        Some("ArrayPattern") => {
+            for element in pattern["elements"].as_array().into_iter().flatten() {
+                pattern_names(element, names);
+            }
+            pattern_names(&pattern["rest"]["argument"], names);
+        }
+        Some("AssignmentPattern") => pattern_names(&pattern["left"], names),
+        Some("RestElement") => pattern_names(&pattern["argument"], names),
+        _ => {}
+    }
+}
+
+fn collect_thrown_errors<'a>(
+    node: &JsonValue,
+    helpers: &BTreeMap<&'a str, &JsonValue>,
+    visited: &mut Vec<&'a str>,
+    scope: &ArgScope,
+    errors: &mut Vec<JsonValue>,
+)
+{
+    match node {
+        JsonValue::Object(fields) => {
+            let shadowed;
+            let names = declared_names(node);
+            let scope = if names.is_empty() {
+                scope
+            } else {
+                shadowed = scope.without(&names);
+                &shadowed
+            };
+
+            let callee = &node["callee"];
+            match (fields.get("type").and_then(JsonValue::as_str), callee["type"].as_str()) {
+                (Some("NewExpression"), Some("Identifier")) if callee["name"].as_str() == Some("ConvexError") => {
+                    if let Some(payload) = node["arguments"].get(0) {
+                        let error = expression_type(payload, scope);
+                        if !errors.contains(&error) {
+                            errors.push(error);
+                        }
+                    }
+                }
+                (Some("CallExpression"), Some("Identifier")) => {
+                    let helper = callee["name"].as_str().and_then(|name| helpers.get_key_value(name));
+                    if let Some((&name, &helper)) = helper {
+                        if !visited.contains(&name) {
+                            visited.push(name);
+                            // Helpers are declared outside the handler and can't see its arguments
+                            collect_thrown_errors(helper, helpers, visited, &ArgScope::default(), errors);
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            for value in fields.values() {
+                collect_thrown_errors(value, helpers, visited, scope, errors);
+            }
+        }
+        JsonValue::Array(items) => {
+            for item in items {
+                collect_thrown_errors(item, helpers, visited, scope, errors);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Infer the data type of a JavaScript expression, falling back to `v.any()`
+fn expression_type(expression: &JsonValue, scope: &ArgScope) -> JsonValue
+{
+    let inferred = match expression["type"].as_str() {
+        Some("StringLiteral") => Some(json!({ "type": "literal", "value": expression })),
+        Some("TemplateLiteral") => Some(json!({ "type": "string" })),
+        Some("Literal") | Some("UnaryExpression") => match literal_node_value(expression) {
+            Some(ConvexValue::Int64(_)) => Some(json!({ "type": "int64" })),
+            Some(ConvexValue::Float64(_)) => Some(json!({ "type": "number" })),
+            Some(ConvexValue::Boolean(_)) => Some(json!({ "type": "boolean" })),
+            _ if expression["raw"].as_str() == Some("null") => Some(json!({ "type": "null" })),
+            _ => None,
+        },
+        // Arguments, either destructured (`{ id }`) or read from the args object (`args.id`)
+        Some("Identifier") => scope.binding(expression["name"].as_str()),
+        Some("StaticMemberExpression") => {
+            scope.member(expression["object"]["name"].as_str(), expression["property"]["name"].as_str())
+        }
+        Some("ObjectExpression") => {
+            let properties = expression["properties"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|prop| prop["type"].as_str() == Some("ObjectProperty") && prop["computed"] != json!(true))
+                .filter_map(|prop| {
+                    let key = prop["key"]["name"].as_str().or_else(|| prop["key"]["value"].as_str())?;
+                    Some((key.to_string(), expression_type(&prop["value"], scope)))
+                })
+                .collect::<serde_json::Map<_, _>>();
+            Some(json!({ "type": "object", "properties": properties }))
+        }
+        _ => None,
+    };
+
+    inferred.unwrap_or_else(|| json!({ "type": "any" }))
+}
+
+/// The ASTs of the source files parsed so far, each with the text it was parsed from.
+///
+/// Keeping a cache around lets repeated generations skip parsing the files that didn't change.
+#[derive(Debug, Default)]
+pub(crate) struct AstCache
+{
+    files: HashMap<PathBuf, (String, JsonValue)>,
+    /// Source text given in memory, read instead of the file at the same path
+    sources: BTreeMap<PathBuf, String>,
+}
+
+impl AstCache
+{
+    /// A cache reading the files in `sources` from memory rather than from disk.
+    ///
+    /// # Errors
+    /// Returns an error if a path cannot be made absolute.
+    pub(crate) fn with_sources(sources: &BTreeMap<PathBuf, String>) -> Result<Self, ConvexTypeGeneratorError>
+    {
+        let sources = sources
+            .iter()
+            .map(|(path, source_text)| Ok((absolute_path(path)?, source_text.clone())))
+            .collect::<Result<_, ConvexTypeGeneratorError>>()?;
+
+        Ok(Self {
+            files: HashMap::new(),
+            sources,
+        })
+    }
+
+    /// Whether a source file exists, in memory or on disk.
+    pub(crate) fn contains(&self, path: &Path) -> bool
+    {
+        absolute_path(path).is_ok_and(|path| self.sources.contains_key(&path) || path.exists())
+    }
+
+    /// Reads a source file and returns its AST, reusing the cached one if the text is unchanged.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or parsed.
+    pub(crate) fn ast(&mut self, path: &Path) -> Result<JsonValue, ConvexTypeGeneratorError>
+    {
+        let absolute = absolute_path(path)?;
+        let source_text = match self.sources.get(&absolute) {
+            Some(source_text) => source_text.clone(),
+            None => std::fs::read_to_string(&absolute).map_err(|error| ConvexTypeGeneratorError::IOError {
+                file: path.to_string_lossy().to_string(),
+                error,
+            })?,
+        };
+
+        if let Some((cached_text, ast)) = self.files.get(&absolute) {
+            if *cached_text == source_text {
+                return Ok(ast.clone());
+            }
+        }
+
+        let ast = generate_ast(path, &source_text)?;
+        self.files.insert(absolute, (source_text, ast.clone()));
+        Ok(ast)
+    }
+}
+
+/// The schema, the function files, and the local files they import, directly or not.
+pub(crate) fn source_files(config: &Configuration, cache: &mut AstCache) -> BTreeSet<PathBuf>
+{
+    let mut files = BTreeSet::new();
+    let mut pending = std::iter::once(&config.schema_path)
+        .chain(&config.function_paths)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    while let Some(file) = pending.pop() {
+        if !files.insert(file.clone()) {
+            continue;
+        }
+        // A file that doesn't parse yet is still watched, its imports are picked up once it does
+        if let Ok(ast) = cache.ast(&file) {
+            pending.extend(relative_imports(&ast, &file));
+        }
+    }
+
+    files
+}
+
+/// The files a module imports or re-exports with relative specifiers like `./validators`.
+///
+/// Convex's own `_generated` files are left out, as `npx convex dev` rewrites them all the time.
+fn relative_imports(ast: &JsonValue, file: &Path) -> Vec<PathBuf>
+{
+    let Some(dir) = file.parent() else {
+        return Vec::new();
+    };
+
+    ast["body"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|statement| statement["source"]["value"].as_str())
+        .filter(|specifier| specifier.starts_with('.') && !specifier.contains("_generated"))
+        .filter_map(|specifier| resolve_import(&dir.join(specifier).components().collect::<PathBuf>()))
+        .collect()
+}
+
+/// Resolves an import specifier the way TypeScript does for the file extensions Convex supports.
+fn resolve_import(base: &Path) -> Option<PathBuf>
+{
+    let mut candidates = vec![base.to_path_buf()];
+    // TypeScript allows importing `./helpers.ts` as `./helpers.js`
+    if base.extension().is_some_and(|extension| extension == "js") {
+        candidates.push(base.with_extension("ts"));
+    }
+    for extension in ["ts", "tsx", "js", "jsx"] {
+        let mut file_name = base.as_os_str().to_os_string();
+        file_name.push(format!(".{}", extension));
+        candidates.push(PathBuf::from(file_name));
+        candidates.push(base.join(format!("index.{}", extension)));
+    }
+
+    candidates.into_iter().find(|candidate| candidate.is_file())
+}
+
+/// Generates an AST from the text of a source file.
+///
+/// # Arguments
+/// * `path` - Path to the source file, used for its extension and in errors
+/// * `source_text` - The contents of the source file
+///
+/// # Errors
+/// Returns an error if the file cannot be parsed or contains invalid syntax
+fn generate_ast(path: &Path, source_text: &str) -> Result<JsonValue, ConvexTypeGeneratorError>
+{
+    let path_str = path.to_string_lossy().to_string();
+    let allocator = Allocator::default();
+
+    if source_text.trim().is_empty() {
+        return Err(ConvexTypeGeneratorError::EmptySchemaFile { file: path_str });
+    }
+
+    let source_type = SourceType::from_path(path).map_err(|_| ConvexTypeGeneratorError::ParsingFailed {
+        file: path_str.clone(),
+        details: "Failed to determine source type".to_string(),
+    })?;
+
+    let mut errors: Vec<OxcDiagnostic> = Vec::new();
+
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    errors.extend(ret.errors);
+
+    if ret.panicked {
+        for error in &errors {
+            eprintln!("{error:?}");
+        }
+        return Err(ConvexTypeGeneratorError::ParsingFailed {
+            file: path_str.clone(),
+            details: "Parser panicked".to_string(),
+        });
+    }
+
+    if ret.program.is_empty() {
+        return Err(ConvexTypeGeneratorError::EmptySchemaFile { file: path_str });
+    }
+
+    let semantics = SemanticBuilder::new().with_check_syntax_error(true).build(&ret.program);
+    errors.extend(semantics.errors);
+
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("{error:?}");
+        }
+        return Err(ConvexTypeGeneratorError::ParsingFailed {
+            file: path_str,
+            details: "Semantic analysis failed".to_string(),
+        });
+    }
+
+    serde_json::to_value(&ret.program).map_err(ConvexTypeGeneratorError::SerializationFailed)
+}
+
+const VALID_TYPES: &[&str] = &[
+    "id", "null", "int64", "number", "boolean", "string", "bytes", "array", "object", "record", "union", "literal",
+    "optional", "any",
+];
+
+/// Checks that a parsed data type names one of Convex's validator types, e.g. `"int64"`.
+///
+/// # Errors
+/// Returns [`ConvexTypeGeneratorError::InvalidType`] listing the valid types otherwise.
+pub fn validate_type_name(type_name: &str) -> Result<(), ConvexTypeGeneratorError>
+{
+    if !VALID_TYPES.contains(&type_name) {
+        return Err(ConvexTypeGeneratorError::InvalidType {
+            found: type_name.to_string(),
+            valid_types: VALID_TYPES.iter().map(|&s| s.to_string()).collect(),
+        });
+    }
+    Ok(())
+}
+
+/// Expand `paginationOptsValidator` and `paginationResultValidator(item)` into the object types they
+/// validate.
+///
+/// The expanded objects keep the name of the validator under `"validator"`, so the generator can
+/// map them to `convex_typegen::pagination::PaginationOpts` and
+/// `convex_typegen::pagination::PaginationResult`.
+fn extract_pagination_type(
+    value: &JsonValue,
+    context: &mut TypeContext,
+) -> Result<Option<JsonValue>, ConvexTypeGeneratorError>
+{
+    let nullable_string = json!({ "type": "union", "variants": [{ "type": "string" }, { "type": "null" }] });
+
+    if value["type"].as_str() == Some("Identifier") && value["name"].as_str() == Some("paginationOptsValidator") {
+        return Ok(Some(json!({
+            "type": "object",
+            "validator": "paginationOptsValidator",
+            "properties": {
+                "numItems": { "type": "number" },
+                "cursor": nullable_string,
+                "endCursor": { "type": "optional", "inner": nullable_string },
+                "id": { "type": "optional", "inner": { "type": "number" } },
+                "maximumRowsRead": { "type": "optional", "inner": { "type": "number" } },
+                "maximumBytesRead": { "type": "optional", "inner": { "type": "number" } },
+            },
+        })));
+    }
+
+    if value["type"].as_str() == Some("CallExpression")
+        && value["callee"]["type"].as_str() == Some("Identifier")
+        && value["callee"]["name"].as_str() == Some("paginationResultValidator")
+    {
+        let item = value["arguments"]
+            .get(0)
+            .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+                context: context.get_error_context(),
+                details: "paginationResultValidator must have an item validator".to_string(),
+            })?;
+        context.type_path.push("page".to_string());
+        let item_type = extract_column_type(&json!({ "value": item }), context)?;
+        context.type_path.pop();
+
+        let page_status = |status: &str| json!({ "type": "literal", "value": { "type": "StringLiteral", "value": status } });
+        return Ok(Some(json!({
+            "type": "object",
+            "validator": "paginationResultValidator",
+            "properties": {
+                "page": { "type": "array", "elements": item_type },
+                "isDone": { "type": "boolean" },
+                "continueCursor": { "type": "string" },
+                "splitCursor": { "type": "optional", "inner": nullable_string },
+                "pageStatus": {
+                    "type": "optional",
+                    "inner": {
+                        "type": "union",
+                        "variants": [page_status("SplitRecommended"), page_status("SplitRequired"), { "type": "null" }],
+                    },
+                },
+            },
+        })));
+    }
+
+    Ok(None)
+}
+
+/// Get the value of a `v.literal(...)` type, if it is a string, number, boolean or bigint literal
+pub fn literal_value(data_type: &JsonValue) -> Option<ConvexValue>
+{
+    literal_node_value(&data_type["value"])
+}
+
+fn literal_node_value(node: &JsonValue) -> Option<ConvexValue>
+{
+    match node["type"].as_str()? {
+        "StringLiteral" => node["value"].as_str().map(|s| ConvexValue::String(s.to_string())),
+        "Literal" => {
+            if let Some(bigint) = node["bigint"].as_str() {
+                return bigint.parse().ok().map(ConvexValue::Int64);
+            }
+            match &node["value"] {
+                JsonValue::Bool(b) => Some(ConvexValue::Boolean(*b)),
+                JsonValue::Number(n) => n.as_f64().map(ConvexValue::Float64),
+                _ => None,
+            }
+        }
+        // Negative numbers are parsed as a unary minus applied to a literal
+        "UnaryExpression" if node["operator"].as_str() == Some("-") => match literal_node_value(&node["argument"])? {
+            ConvexValue::Float64(f) => Some(ConvexValue::Float64(-f)),
+            ConvexValue::Int64(i) => Some(ConvexValue::Int64(-i)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Get the table name of a `v.id("table")` type
+pub fn id_table_name(data_type: &JsonValue) -> Option<&str>
+{
+    data_type["arguments"][0]["value"].as_str()
+}
+
+/// Render a parsed data type as the `v.*` validator expression it came from
+pub fn describe_data_type(data_type: &JsonValue) -> String
+{
+    // Pagination validators are described by name rather than by their expanded objects
+    match data_type["validator"].as_str() {
+        Some("paginationOptsValidator") => return "paginationOptsValidator".to_string(),
+        Some("paginationResultValidator") => {
+            return format!(
+                "paginationResultValidator({})",
+                describe_data_type(&data_type["properties"]["page"]["elements"])
+            )
+        }
+        _ => {}
+    }
+
+    describe_validator(data_type).unwrap_or_else(|| "v.any()".to_string())
+}
+
+/// Render a data type as a `v.*` expression, or `None` if it is not a valid validator
+fn describe_validator(data_type: &JsonValue) -> Option<String>
+{
+    Some(match data_type["type"].as_str()? {
+        "id" => format!("v.id({:?})", id_table_name(data_type)?),
+        "null" => "v.null()".to_string(),
+        "int64" => "v.int64()".to_string(),
+        "number" => "v.number()".to_string(),
+        "boolean" => "v.boolean()".to_string(),
+        "string" => "v.string()".to_string(),
+        "bytes" => "v.bytes()".to_string(),
+        "any" => "v.any()".to_string(),
+        "literal" => match literal_value(data_type)? {
+            ConvexValue::String(s) => format!("v.literal({:?})", s),
+            ConvexValue::Int64(i) => format!("v.literal({}n)", i),
+            ConvexValue::Float64(n) => format!("v.literal({})", n),
+            ConvexValue::Boolean(b) => format!("v.literal({})", b),
+            other => format!("v.literal({:?})", other),
+        },
+        "array" => format!("v.array({})", describe_validator(&data_type["elements"])?),
+        "optional" => format!("v.optional({})", describe_validator(&data_type["inner"])?),
+        "record" => format!(
+            "v.record({}, {})",
+            describe_validator(&data_type["keyType"])?,
+            describe_validator(&data_type["valueType"])?
+        ),
+        "union" => {
+            let variants = data_type["variants"]
+                .as_array()?
+                .iter()
+                .map(describe_validator)
+                .collect::<Option<Vec<_>>>()?;
+            format!("v.union({})", variants.join(", "))
+        }
+        "object" => {
+            let fields = match data_type["properties"].as_object() {
+                Some(properties) => properties
+                    .iter()
+                    .map(|(name, field)| Some(format!("{}: {}", name, describe_validator(field)?)))
+                    .collect::<Option<Vec<_>>>()?,
+                None => Vec::new(),
+            };
+            if fields.is_empty() {
+                "v.object({})".to_string()
+            } else {
+                format!("v.object({{ {} }})", fields.join(", "))
+            }
+        }
+        _ => return None,
+    })
+}
+
+/// Returns the marker type for a function constructor, e.g. `Query` for `internalQuery`.
+pub(crate) fn function_kind(type_: &str) -> Option<&'static str>
+{
+    match type_ {
+        "query" | "internalQuery" => Some("Query"),
+        "mutation" | "internalMutation" => Some("Mutation"),
+        "action" | "internalAction" => Some("Action"),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default)]
+struct TypeContext
+{
+    /// Stack of type paths being processed (includes type name and path)
+    type_stack: Vec<(String, String)>, // (type_name, full_path)
+    /// Current file being processed - used for error context
+    file_name: String,
+    /// Current path in the type structure
+    type_path: Vec<String>,
+}
+
+impl TypeContext
+{
+    fn new(file_name: String) -> Self
+    {
+        Self {
+            file_name,
+            type_stack: Vec::new(),
+            type_path: Vec::new(),
+        }
+    }
+
+    fn push_type(&mut self, type_name: &str) -> Result<(), ConvexTypeGeneratorError>
+    {
+        let current_path = self.type_path.join(".");
+
+        // Only check for circular references in object types
+        // Arrays and other container types can be nested
+        if type_name == "object" {
+            let full_path = if current_path.is_empty() {
+                type_name.to_string()
+            } else {
+                format!("{}.{}", current_path, type_name)
+            };
+
+            // Check if this exact path has been seen before
+            if self.type_stack.iter().any(|(_, path)| path == &full_path) {
+                return Err(ConvexTypeGeneratorError::CircularReference {
+                    path: self.type_stack.iter().map(|(_, path)| path.clone()).collect(),
+                });
+            }
+            self.type_stack.push((type_name.to_string(), full_path));
+        }
+        Ok(())
+    }
+
+    /// Get the current context for error messages
+    fn get_error_context(&self) -> String
+    {
+        format!("{}:{}", self.file_name, self.type_path.join("."))
+    }
+
+    /// Removes the most recently pushed type from the stack
+    fn pop_type(&mut self)
+    {
+        // Only pop if the last type was an object (matches push_type behavior)
+        if let Some((type_name, _)) = self.type_stack.last() {
+            if type_name == "object" {
+                self.type_stack.pop();
+            }
+        }
+    }
+}
+
+fn check_circular_references(type_obj: &JsonValue, context: &mut TypeContext) -> Result<(), ConvexTypeGeneratorError>
+{
+    let type_name = type_obj["type"]
+        .as_str()
+        .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+            context: context.type_path.join("."),
+            details: "Missing type name".to_string(),
+        })?;
+
+    context.push_type(type_name)?;
+
+    match type_name {
+        "optional" => {
+            if let Some(inner) = type_obj.get("inner") {
+                context.type_path.push("inner".to_string());
+                check_circular_references(inner, context)?;
+                context.type_path.pop();
+            }
+        }
+        "array" => {
+            if let Some(elements) = type_obj.get("elements") {
+                context.type_path.push("elements".to_string());
+                check_circular_references(elements, context)?;
+                context.type_path.pop();
+            }
+        }
+        "object" => {
+            if let Some(properties) = type_obj.get("properties") {
+                if let Some(props) = properties.as_object() {
+                    for (prop_name, prop_type) in props {
+                        context.type_path.push(prop_name.to_string());
+                        check_circular_references(prop_type, context)?;
+                        context.type_path.pop();
+                    }
+                }
+            }
+        }
+        "record" => {
+            // Check key type
+            if let Some(key_type) = type_obj.get("keyType") {
+                context.type_path.push("keyType".to_string());
+                check_circular_references(key_type, context)?;
+                context.type_path.pop();
+            }
+            // Check value type
+            if let Some(value_type) = type_obj.get("valueType") {
+                context.type_path.push("valueType".to_string());
+                check_circular_references(value_type, context)?;
+                context.type_path.pop();
+            }
+        }
+        "union" | "intersection" => {
+            if let Some(variants) = type_obj["variants"].as_array() {
+                for (i, variant) in variants.iter().enumerate() {
+                    context.type_path.push(format!("variant_{}", i));
+                    check_circular_references(variant, context)?;
+                    context.type_path.pop();
+                }
+            }
+        }
+        _ => {} // Other types don't have nested types
+    }
+
+    context.pop_type();
+    Ok(())
+}
@@ -0,0 +1,375 @@
+//! The parser and code generator behind `convex-typegen`.
+//!
+//! Most users want the `convex-typegen` crate, which re-exports everything here along with the
+//! runtime the generated code depends on. This crate exists so the `schema!` macro can run the
+//! generator without depending on that runtime.
+
+mod codegen;
+pub mod convex;
+pub mod errors;
+#[cfg(feature = "watch")]
+pub mod watch;
+
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use codegen::{generate_code, render_code};
+use convex::{
+    absolute_path, common_directory, create_functions_ast, create_schema_ast, parse_function_ast, parse_schema_ast,
+    source_files, AstCache, ConvexFunctions, ConvexSchema,
+};
+use errors::ConvexTypeGeneratorError;
+use similar::TextDiff;
+
+/// Configuration options for the type generator.
+///
+/// Set the fields directly, or start from the defaults and chain the `with_*` methods:
+///
+/// ```no_run
+/// use convex_typegen_codegen::Configuration;
+///
+/// let config = Configuration::default()
+///     .with_function_paths(["convex/games.ts"])
+///     .with_rerun_if_changed(true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Configuration
+{
+    /// Path to the Convex schema file (default: "convex/schema.ts")
+    pub schema_path: PathBuf,
+
+    /// Output file path for generated Rust types (default: "src/convex_types.rs")
+    pub out_file: String,
+
+    /// Write the generated types to Cargo's `OUT_DIR`, keeping only the file name of `out_file`,
+    /// so they can be included with `convex_typegen::include_convex_types!` (default: false)
+    pub out_dir: bool,
+
+    /// Paths to Convex function files for generating function argument types
+    pub function_paths: Vec<PathBuf>,
+
+    /// Print a `cargo:rerun-if-changed` directive for every file and directory the generator reads,
+    /// so a build script reruns whenever the Convex sources change (default: false)
+    pub rerun_if_changed: bool,
+
+    /// Source text to use instead of reading a file, keyed by the path used in `schema_path` or
+    /// `function_paths`. Files given here don't need to exist on disk (default: empty)
+    pub sources: BTreeMap<PathBuf, String>,
+}
+
+impl Default for Configuration
+{
+    fn default() -> Self
+    {
+        Self {
+            schema_path: PathBuf::from("convex/schema.ts"),
+            out_file: "src/convex_types.rs".to_string(),
+            out_dir: false,
+            function_paths: Vec::new(),
+            rerun_if_changed: false,
+            sources: BTreeMap::new(),
+        }
+    }
+}
+
+impl Configuration
+{
+    /// Sets [`schema_path`](Configuration::schema_path).
+    pub fn with_schema_path(mut self, schema_path: impl Into<PathBuf>) -> Self
+    {
+        self.schema_path = schema_path.into();
+        self
+    }
+
+    /// Sets [`out_file`](Configuration::out_file).
+    pub fn with_out_file(mut self, out_file: impl Into<String>) -> Self
+    {
+        self.out_file = out_file.into();
+        self
+    }
+
+    /// Sets [`out_dir`](Configuration::out_dir).
+    pub fn with_out_dir(mut self, out_dir: bool) -> Self
+    {
+        self.out_dir = out_dir;
+        self
+    }
+
+    /// Replaces [`function_paths`](Configuration::function_paths).
+    pub fn with_function_paths<P: Into<PathBuf>>(mut self, function_paths: impl IntoIterator<Item = P>) -> Self
+    {
+        self.function_paths = function_paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets [`rerun_if_changed`](Configuration::rerun_if_changed).
+    pub fn with_rerun_if_changed(mut self, rerun_if_changed: bool) -> Self
+    {
+        self.rerun_if_changed = rerun_if_changed;
+        self
+    }
+
+    /// Adds the source text of the file at `path` to [`sources`](Configuration::sources).
+    pub fn with_source(mut self, path: impl Into<PathBuf>, source: impl Into<String>) -> Self
+    {
+        self.sources.insert(path.into(), source.into());
+        self
+    }
+
+    /// The path the generated types are written to.
+    ///
+    /// # Errors
+    /// Returns an error if [`out_dir`](Configuration::out_dir) is set but `OUT_DIR` is not, i.e. when
+    /// not running in a build script.
+    pub fn output_path(&self) -> Result<PathBuf, ConvexTypeGeneratorError>
+    {
+        self.output_path_in(std::env::var_os("OUT_DIR").as_deref())
+    }
+
+    /// The path the generated types are written to, given the value of `OUT_DIR`.
+    ///
+    /// # Errors
+    /// Returns an error if [`out_dir`](Configuration::out_dir) is set but `out_dir` is `None`.
+    pub fn output_path_in(&self, out_dir: Option<&OsStr>) -> Result<PathBuf, ConvexTypeGeneratorError>
+    {
+        if !self.out_dir {
+            return Ok(PathBuf::from(&self.out_file));
+        }
+
+        let out_dir = out_dir.ok_or(ConvexTypeGeneratorError::MissingOutDir)?;
+        let file_name = Path::new(&self.out_file)
+            .file_name()
+            .ok_or_else(|| ConvexTypeGeneratorError::InvalidPath(self.out_file.clone()))?;
+        Ok(Path::new(out_dir).join(file_name))
+    }
+
+    /// The files and directories the generator reads.
+    ///
+    /// These are the schema, the function files, the local files they import, and the directories
+    /// containing the schema and function files, so adding a file to the Convex directory is noticed
+    /// too. The current directory is left out, as watching it would include the build output.
+    pub fn watched_paths(&self) -> Vec<PathBuf>
+    {
+        // Sources whose path can't be made absolute are read from disk, as `generate` would fail anyway
+        self.watched_paths_in(&mut AstCache::with_sources(&self.sources).unwrap_or_default())
+    }
+
+    fn watched_paths_in(&self, cache: &mut AstCache) -> Vec<PathBuf>
+    {
+        let files = std::iter::once(&self.schema_path).chain(&self.function_paths);
+        let mut paths = files.clone().cloned().collect::<Vec<_>>();
+
+        let imports = source_files(self, cache);
+        paths.extend(imports.into_iter().filter(|file| !files.clone().any(|path| path == file)));
+
+        for file in files {
+            let Some(dir) = file.parent() else {
+                continue;
+            };
+            if dir.as_os_str().is_empty() || dir == Path::new(".") || paths.iter().any(|path| path == dir) {
+                continue;
+            }
+            paths.push(dir.to_path_buf());
+        }
+
+        paths
+    }
+}
+
+/// Generates Rust types from Convex schema and function definitions.
+///
+/// # Arguments
+/// * `config` - Configuration options for the type generation process
+///
+/// When called from a build script, set [`Configuration::rerun_if_changed`] to have Cargo rerun it
+/// whenever the Convex sources change.
+///
+/// # Returns
+/// * `Ok(())` if type generation succeeds
+/// * `Err(ConvexTypeGeneratorError)` if an error occurs during generation
+///
+/// # Errors
+/// This function can fail for several reasons:
+/// * Schema file not found
+/// * Invalid schema structure
+/// * IO errors when reading/writing files
+/// * Parse errors in schema or function files
+pub fn generate(config: Configuration) -> Result<(), ConvexTypeGeneratorError>
+{
+    let mut cache = AstCache::with_sources(&config.sources)?;
+
+    // Printed first, so a failed generation still reruns once the sources are fixed
+    if config.rerun_if_changed {
+        for path in config.watched_paths_in(&mut cache) {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+
+    let parsed = parse_sources(&config, &mut cache)?;
+    generate_code(&config.output_path()?, parsed)?;
+
+    Ok(())
+}
+
+/// Generates Rust types from Convex schema and function definitions, returning the code instead of
+/// writing it to [`Configuration::out_file`].
+///
+/// Together with [`Configuration::sources`], this works without touching the file system at all.
+///
+/// # Errors
+/// Returns the same errors as [`generate`], except for writing the output.
+pub fn generate_to_string(config: Configuration) -> Result<String, ConvexTypeGeneratorError>
+{
+    let parsed = parse_sources(&config, &mut AstCache::with_sources(&config.sources)?)?;
+    render_code(parsed)
+}
+
+/// Checks that the output file contains the code [`generate`] would write, without writing it.
+///
+/// Meant for CI, where a committed output file must be regenerated whenever the Convex sources
+/// change. A missing output file counts as out of date.
+///
+/// # Errors
+/// Returns [`ConvexTypeGeneratorError::OutOfDate`] with a unified diff if the output file differs,
+/// and the errors of [`generate`] if the sources cannot be parsed.
+pub fn check(config: Configuration) -> Result<(), ConvexTypeGeneratorError>
+{
+    let out_file = config.output_path()?;
+    let generated = generate_to_string(config)?;
+
+    let existing = match std::fs::read_to_string(&out_file) {
+        Ok(existing) => existing,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(ConvexTypeGeneratorError::from(error).with_file_context(out_file.to_string_lossy())),
+    };
+
+    if existing == generated {
+        return Ok(());
+    }
+
+    let file = out_file.to_string_lossy().to_string();
+    let diff = TextDiff::from_lines(&existing, &generated)
+        .unified_diff()
+        .header(&file, &format!("{} (generated)", file))
+        .to_string();
+
+    Err(ConvexTypeGeneratorError::OutOfDate { file, diff })
+}
+
+/// Parses the schema and function files of a configuration, the same way [`generate`] does.
+///
+/// Function modules are named relative to the directory containing the schema.
+///
+/// # Errors
+/// Returns an error if the schema file is missing, or a file cannot be read or parsed.
+pub fn parse(config: &Configuration) -> Result<(ConvexSchema, ConvexFunctions), ConvexTypeGeneratorError>
+{
+    parse_sources(config, &mut AstCache::with_sources(&config.sources)?)
+}
+
+/// Parses the schema and function files the generator works from.
+pub(crate) fn parse_sources(
+    config: &Configuration,
+    cache: &mut AstCache,
+) -> Result<(ConvexSchema, ConvexFunctions), ConvexTypeGeneratorError>
+{
+    if !cache.contains(&config.schema_path) {
+        return Err(ConvexTypeGeneratorError::MissingSchemaFile);
+    }
+
+    // Paths are compared as absolute paths, so relative and absolute spellings of a file match
+    let schema_path = absolute_path(&config.schema_path)?;
+
+    // The schema lives at the root of the Convex functions directory
+    let functions_dir = schema_path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    let parsed_schema = parse_schema_ast(create_schema_ast(schema_path, cache)?)?;
+    let functions_ast = create_functions_ast(config.function_paths.clone(), &functions_dir, cache)?;
+    let parsed_functions = parse_function_ast(functions_ast)?;
+
+    Ok((parsed_schema, parsed_functions))
+}
+
+/// Parses a Convex schema file into its table definitions.
+///
+/// The result is the same representation the generator works from, and can be used to drive
+/// runtime conversion of values.
+///
+/// # Errors
+/// Returns an error if the schema file is missing, cannot be parsed or has an invalid structure.
+pub fn parse_schema(schema_path: PathBuf) -> Result<ConvexSchema, ConvexTypeGeneratorError>
+{
+    let schema_ast = create_schema_ast(schema_path, &mut AstCache::default())?;
+    parse_schema_ast(schema_ast)
+}
+
+/// Parses Convex function files into their definitions, including their argument types.
+///
+/// Module paths are taken relative to the deepest directory containing all of the files.
+///
+/// # Errors
+/// Returns an error if a function file cannot be read or parsed.
+pub fn parse_functions(function_paths: Vec<PathBuf>) -> Result<ConvexFunctions, ConvexTypeGeneratorError>
+{
+    let functions_dir = common_directory(&function_paths)?;
+    let functions_ast = create_functions_ast(function_paths, &functions_dir, &mut AstCache::default())?;
+    parse_function_ast(functions_ast)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::fs;
+
+    use tempdir::TempDir;
+
+    use super::*;
+
+    fn setup_test_dir() -> TempDir
+    {
+        TempDir::new("convex_typegen_test").expect("Failed to create temp directory")
+    }
+
+    #[test]
+    fn test_configuration_default()
+    {
+        let config = Configuration::default();
+        assert_eq!(config.schema_path, PathBuf::from("convex/schema.ts"));
+        assert_eq!(config.out_file, "src/convex_types.rs");
+        assert!(config.function_paths.is_empty());
+    }
+
+    #[test]
+    fn test_missing_schema_file()
+    {
+        let temp_dir = setup_test_dir();
+        let config = Configuration {
+            schema_path: temp_dir.path().join("nonexistent.ts"),
+            ..Default::default()
+        };
+
+        match generate(config) {
+            Err(ConvexTypeGeneratorError::MissingSchemaFile) => (),
+            other => panic!("Expected MissingSchemaFile error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_empty_schema_file()
+    {
+        let temp_dir = setup_test_dir();
+        let schema_path = temp_dir.path().join("schema.ts");
+        fs::write(&schema_path, "").unwrap();
+
+        let config = Configuration {
+            schema_path,
+            ..Default::default()
+        };
+
+        match generate(config) {
+            Err(ConvexTypeGeneratorError::EmptySchemaFile { .. }) => (),
+            other => panic!("Expected EmptySchemaFile error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Regenerating the types whenever the Convex sources change.
+
+use std::collections::BTreeSet;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::codegen::render_code;
+use crate::convex::{source_files, AstCache};
+use crate::errors::ConvexTypeGeneratorError;
+use crate::{parse_sources, Configuration};
+
+/// How often the sources are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long the sources have to stay unchanged before regenerating, so saving several files at
+/// once only regenerates once.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// The outcome of a generation while watching.
+#[derive(Debug)]
+pub enum WatchEvent
+{
+    /// The generated code changed and was written to the output file
+    Generated
+    {
+        /// Path the code was written to
+        out_file: PathBuf,
+        /// How long parsing and generating took
+        elapsed: Duration,
+    },
+
+    /// The output file already contains the generated code, so it wasn't written
+    Unchanged
+    {
+        /// Path of the output file
+        out_file: PathBuf,
+    },
+
+    /// The sources could not be parsed, the output file is left as it was
+    Failed(ConvexTypeGeneratorError),
+}
+
+/// Generates the types, then regenerates them whenever the schema, a function file or a file they
+/// import changes.
+///
+/// `on_event` is called after every generation, and watching stops once it returns
+/// [`ControlFlow::Break`]. Errors in the sources are passed to `on_event` rather than ending the
+/// watch. Only the files that changed are parsed again, and the output file is only written when
+/// the generated code differs, so builds depending on it don't rerun needlessly.
+///
+/// # Errors
+/// Returns an error if the output path is invalid.
+pub fn watch(
+    config: Configuration,
+    mut on_event: impl FnMut(WatchEvent) -> ControlFlow<()>,
+) -> Result<(), ConvexTypeGeneratorError>
+{
+    let out_file = config.output_path()?;
+    let mut cache = AstCache::with_sources(&config.sources)?;
+
+    loop {
+        // Taken before reading the files, so changes made while generating aren't missed
+        let files = source_files(&config, &mut cache);
+        let versions = file_versions(&files);
+
+        let started = Instant::now();
+        let event = match regenerate(&config, &out_file, &mut cache) {
+            Ok(true) => WatchEvent::Generated {
+                out_file: out_file.clone(),
+                elapsed: started.elapsed(),
+            },
+            Ok(false) => WatchEvent::Unchanged {
+                out_file: out_file.clone(),
+            },
+            Err(error) => WatchEvent::Failed(error),
+        };
+        if on_event(event).is_break() {
+            return Ok(());
+        }
+
+        wait_for_change(&files, versions);
+    }
+}
+
+/// Parses the sources and writes the generated code, returning whether the output file changed.
+fn regenerate(config: &Configuration, out_file: &Path, cache: &mut AstCache) -> Result<bool, ConvexTypeGeneratorError>
+{
+    let code = render_code(parse_sources(config, cache)?)?;
+    if std::fs::read_to_string(out_file).is_ok_and(|existing| existing == code) {
+        return Ok(false);
+    }
+
+    std::fs::write(out_file, code)
+        .map_err(|error| ConvexTypeGeneratorError::from(error).with_file_context(out_file.to_string_lossy()))?;
+
+    Ok(true)
+}
+
+/// The modification time and length of each file, `None` for files that can't be read.
+fn file_versions(files: &BTreeSet<PathBuf>) -> Vec<Option<(SystemTime, u64)>>
+{
+    files
+        .iter()
+        .map(|file| {
+            let metadata = std::fs::metadata(file).ok()?;
+            Some((metadata.modified().ok()?, metadata.len()))
+        })
+        .collect()
+}
+
+/// Polls the files until one of them changes, and then until they stay unchanged for [`DEBOUNCE`].
+fn wait_for_change(files: &BTreeSet<PathBuf>, mut versions: Vec<Option<(SystemTime, u64)>>)
+{
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let current = file_versions(files);
+        if current != versions {
+            versions = current;
+            break;
+        }
+    }
+
+    let mut unchanged_since = Instant::now();
+    while unchanged_since.elapsed() < DEBOUNCE {
+        std::thread::sleep(POLL_INTERVAL);
+        let current = file_versions(files);
+        if current != versions {
+            versions = current;
+            unchanged_since = Instant::now();
+        }
+    }
+}
@@ -2,14 +2,12 @@ use convex_typegen::{generate, Configuration};
 
 fn main()
 {
-    // Rebuild if the schema or games files change
-    println!("cargo:rerun-if-changed=convex/schema.ts");
-    println!("cargo:rerun-if-changed=convex/games.ts");
-
-    let config = Configuration {
-        function_paths: vec![std::path::PathBuf::from("convex/games.ts")],
-        ..Default::default()
-    };
+    let config = Configuration::default()
+        .with_function_paths(["convex/games.ts"])
+        // Keep the generated types out of the source tree, see `mod convex_types` in main.rs
+        .with_out_dir(true)
+        // Rebuild when anything in the convex directory changes
+        .with_rerun_if_changed(true);
 
     // Generate the types
     match generate(config) {
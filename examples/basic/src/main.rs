@@ -1,10 +1,14 @@
-mod convex_types;
+mod convex_types
+{
+    convex_typegen::include_convex_types!();
+}
 
 use std::io::{self, Write};
 
-use convex::{ConvexClient, Value as ConvexValue};
+use convex::ConvexClient;
 use convex_typegen::convex::ConvexClientExt;
-use convex_types::{GetGameArgs, LossGameArgs, WinGameArgs};
+use convex_typegen::decode::from_function_result;
+use convex_types::{api, GamesTable, GetGameArgs, LossGameArgs, WinGameArgs};
 use rand::Rng;
 
 const CONVEX_URL: &str = "https://notable-orca-705.convex.cloud";
@@ -15,22 +19,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>>
     let mut client = ConvexClient::new(CONVEX_URL).await?;
 
     // Get current game stats using the extension trait
-    let args_map = ConvexClient::prepare_args(GetGameArgs {});
-    let game_stats = client.query(GetGameArgs::FUNCTION_PATH, args_map).await?;
+    let args_map = ConvexClient::try_prepare_args(GetGameArgs {})?;
+    let game_stats = client.query(api::games::getGame.path(), args_map).await?;
 
     println!("Initial game stats response: {:?}", game_stats);
 
-    let (wins, losses) = match game_stats {
-        convex::FunctionResult::Value(value) => {
-            if let ConvexValue::Object(obj) = value {
-                let win_count = obj.get("win_count").map(extract_float_value).unwrap_or(0.0);
-                let loss_count = obj.get("loss_count").map(extract_float_value).unwrap_or(0.0);
-                (win_count as i32, loss_count as i32)
-            } else {
-                (0, 0)
-            }
-        }
-        _ => (0, 0),
+    let (wins, losses) = match from_function_result::<Option<GamesTable>>(game_stats)? {
+        Some(game) => (game.win_count as i32, game.loss_count as i32),
+        None => (0, 0),
     };
 
     println!("Welcome to the Number Guessing Game!");
@@ -64,8 +60,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>>
             std::cmp::Ordering::Equal => {
                 println!("Congratulations! You won in {} attempts!", attempts);
                 // Save win to Convex using winGame mutation
-                let args_map = ConvexClient::prepare_args(WinGameArgs {});
-                match client.mutation(WinGameArgs::FUNCTION_PATH, args_map).await {
+                match client.call_mutation(WinGameArgs {}).await {
                     Ok(result) => println!("Save win result: {:?}", result),
                     Err(e) => println!("Error saving win: {:?}", e),
                 }
@@ -76,8 +71,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>>
         if attempts >= MAX_ATTEMPTS {
             println!("Sorry, you've run out of attempts! The number was {}", secret_number);
             // Save loss to Convex using lossGame mutation
-            let args_map = ConvexClient::prepare_args(LossGameArgs {});
-            match client.mutation(LossGameArgs::FUNCTION_PATH, args_map).await {
+            match client.call_mutation(LossGameArgs {}).await {
                 Ok(_) => (),
                 Err(e) => println!("Error saving loss: {:?}", e),
             }
@@ -91,15 +85,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>>
     tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
 
     // Get and display updated stats
-    let args_map = ConvexClient::prepare_args(GetGameArgs {});
-    match client.query(GetGameArgs::FUNCTION_PATH, args_map).await {
+    let args_map = ConvexClient::try_prepare_args(GetGameArgs {})?;
+    match client.query(api::games::getGame.path(), args_map).await {
         Ok(updated_stats) => {
-            if let convex::FunctionResult::Value(value) = updated_stats {
-                if let ConvexValue::Object(obj) = value {
-                    let win_count = obj.get("win_count").map(extract_float_value).unwrap_or(0.0);
-                    let loss_count = obj.get("loss_count").map(extract_float_value).unwrap_or(0.0);
-                    println!("\nUpdated record - Wins: {}, Losses: {}", win_count as i32, loss_count as i32);
-                }
+            if let Ok(Some(game)) = from_function_result::<Option<GamesTable>>(updated_stats) {
+                println!("\nUpdated record - Wins: {}, Losses: {}", game.win_count as i32, game.loss_count as i32);
             }
         }
         Err(e) => println!("Error getting updated stats: {:?}", e),
@@ -107,12 +97,3 @@ async fn main() -> Result<(), Box<dyn std::error::Error>>
 
     Ok(())
 }
-
-fn extract_float_value(value: &ConvexValue) -> f64
-{
-    if let ConvexValue::Float64(f) = value {
-        *f
-    } else {
-        0.0
-    }
-}
@@ -0,0 +1,122 @@
+use std::path::{Path, PathBuf};
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Ident, LitStr, Token};
+
+/// Generates the Convex types at compile time, without a build script.
+///
+/// Takes the Convex directory, whose `schema.ts` is parsed, and optionally a glob of function
+/// files. Paths are relative to the crate root. Files in `_generated` and the schema itself are
+/// never treated as function files.
+///
+/// Re-exported as `convex_typegen::schema!` with the `macros` feature, which is how it's meant to
+/// be used, as the generated code refers to `convex_typegen`.
+///
+/// ```ignore
+/// mod convex_types {
+///     convex_typegen::schema!("convex", functions = "convex/**/*.ts");
+/// }
+/// ```
+///
+/// Editing a parsed file recompiles the crate, but adding a file matching the glob does not.
+#[proc_macro]
+pub fn schema(input: TokenStream) -> TokenStream
+{
+    let input = parse_macro_input!(input as SchemaInput);
+
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// The arguments of [`schema!`], e.g. `"convex", functions = "convex/**/*.ts"`.
+struct SchemaInput
+{
+    convex_dir: LitStr,
+    functions: Option<LitStr>,
+}
+
+impl Parse for SchemaInput
+{
+    fn parse(input: ParseStream) -> syn::Result<Self>
+    {
+        let convex_dir = input.parse()?;
+        let mut functions = None;
+
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let option: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match option.to_string().as_str() {
+                "functions" => functions = Some(input.parse()?),
+                _ => return Err(syn::Error::new(option.span(), "unknown option, expected `functions`")),
+            }
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("expected `,`"));
+        }
+
+        Ok(Self { convex_dir, functions })
+    }
+}
+
+fn expand(input: &SchemaInput) -> syn::Result<proc_macro2::TokenStream>
+{
+    let root = std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .ok_or_else(|| syn::Error::new(Span::call_site(), "CARGO_MANIFEST_DIR is not set"))?;
+
+    let convex_dir = root.join(input.convex_dir.value());
+    let schema_path = convex_dir.join("schema.ts");
+    let function_paths = match &input.functions {
+        Some(pattern) => function_files(&root, &convex_dir, pattern)?,
+        None => Vec::new(),
+    };
+
+    let config = convex_typegen_codegen::Configuration::default()
+        .with_schema_path(schema_path.clone())
+        .with_function_paths(function_paths.clone());
+    let code = convex_typegen_codegen::generate_to_string(config).map_err(|error| {
+        syn::Error::new(
+            Span::call_site(),
+            format!("Failed to generate Convex types from '{}': {}", convex_dir.display(), error),
+        )
+    })?;
+    let items: proc_macro2::TokenStream = code
+        .parse()
+        .map_err(|error| syn::Error::new(Span::call_site(), format!("convex-typegen generated invalid code: {}", error)))?;
+
+    // Proc macros aren't rerun when the files they read change, unless the files are included
+    let dependencies = std::iter::once(&schema_path)
+        .chain(&function_paths)
+        .map(|path| path.to_string_lossy().to_string());
+
+    Ok(quote! {
+        #(const _: &[u8] = include_bytes!(#dependencies);)*
+        #items
+    })
+}
+
+/// The function files matching the `functions` glob, in a stable order
+fn function_files(root: &Path, convex_dir: &Path, pattern: &LitStr) -> syn::Result<Vec<PathBuf>>
+{
+    let full_pattern = root.join(pattern.value());
+    let paths = glob::glob(&full_pattern.to_string_lossy())
+        .map_err(|error| syn::Error::new(pattern.span(), format!("Invalid glob: {}", error)))?;
+
+    let mut files = Vec::new();
+    for path in paths {
+        let path = path.map_err(|error| syn::Error::new(pattern.span(), error.to_string()))?;
+        let generated = path.components().any(|component| component.as_os_str() == "_generated");
+        if path.is_file() && !generated && path != convex_dir.join("schema.ts") {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    Ok(files)
+}
@@ -0,0 +1,3 @@
+// Stands in for the code generated by `npx convex dev`, which the macro must skip
+export const query = undefined as any;
+export const mutation = query(;
@@ -0,0 +1,31 @@
+import { mutation, query } from "./_generated/server";
+import { ConvexError, v } from "convex/values";
+
+export const list = query({
+    args: { author: v.optional(v.string()) },
+    handler: async (ctx, args) => {
+        return await ctx.db.query("messages").collect();
+    },
+});
+
+export const send = mutation({
+    args: { author: v.string(), body: v.string() },
+    returns: v.id("messages"),
+    handler: async (ctx, args) => {
+        return await ctx.db.insert("messages", args);
+    },
+});
+
+export const edit = mutation({
+    args: { id: v.id("messages"), body: v.string() },
+    handler: async (ctx, { id, body }) => {
+        const message = await ctx.db.get(id);
+        if (message === null) {
+            throw new ConvexError({ code: "NOT_FOUND", id });
+        }
+        if (body.length > 280) {
+            throw new ConvexError({ code: "TOO_LONG", limit: 280 });
+        }
+        await ctx.db.patch(id, { body });
+    },
+});
@@ -0,0 +1,61 @@
+mod convex_types
+{
+    convex_typegen::schema!("tests/convex", functions = "tests/convex/**/*.ts");
+}
+
+use convex::{ConvexError, FunctionResult, Value as ConvexValue};
+use convex_typegen::client::ConvexFunctionArgs;
+use convex_typegen::convex::ConvexClientExt;
+use convex_typegen::errors::{ConvexCallError, TypedCallError};
+use convex_types::{EditArgs, EditError, MessagesTable, SendArgs};
+use futures::executor::block_on;
+
+#[test]
+fn test_schema_macro_generates_types()
+{
+    let message = MessagesTable {
+        author: "Ada".to_string(),
+        body: "hello".to_string(),
+    };
+    assert!(message.validate().is_ok());
+
+    assert_eq!(<SendArgs as ConvexFunctionArgs>::PATH, "messages:send");
+    let paths = convex_types::FUNCTIONS
+        .iter()
+        .map(|function| function.path)
+        .collect::<Vec<_>>();
+    assert_eq!(paths, vec!["messages:list", "messages:send", "messages:edit"]);
+}
+
+fn thrown(data: serde_json::Value) -> FunctionResult
+{
+    FunctionResult::ConvexError(ConvexError {
+        message: "Uncaught ConvexError".to_string(),
+        data: ConvexValue::try_from(data).unwrap(),
+    })
+}
+
+#[test]
+fn test_schema_macro_decodes_thrown_errors()
+{
+    let edit = || EditArgs {
+        id: "message_1".to_string(),
+        body: "hello".to_string(),
+    };
+    let mut mock = convex_types::mock_transport();
+
+    mock.respond_with::<EditArgs>(thrown(serde_json::json!({ "code": "NOT_FOUND", "id": "message_1" })));
+    let result = block_on(mock.try_call(edit()));
+    assert!(matches!(result, Err(TypedCallError::Thrown { data: EditError::NotFound { id }, .. }) if id == "message_1"));
+
+    mock.respond_with::<EditArgs>(thrown(serde_json::json!({ "code": "TOO_LONG", "limit": 280.0 })));
+    let result = block_on(mock.try_call(edit()));
+    assert!(matches!(result, Err(TypedCallError::Thrown { data: EditError::TooLong { limit }, .. }) if limit == 280.0));
+
+    mock.respond_with::<EditArgs>(FunctionResult::ErrorMessage("Server error".to_string()));
+    let result = block_on(mock.try_call(edit()));
+    assert!(matches!(
+        result,
+        Err(TypedCallError::Call(ConvexCallError::FunctionFailed(_)))
+    ));
+}
@@ -0,0 +1,145 @@
+use convex::ConvexClient;
+use futures::StreamExt;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::client::{Action, ConvexFunctionArgs, ConvexTransport, Mutation, Query, TypedSubscription};
+use crate::convex::ConvexClientExt;
+use crate::errors::ConvexCallError;
+
+/// A blocking wrapper around [`convex::ConvexClient`], enabled with the `blocking` feature.
+///
+/// The client owns a small tokio runtime that keeps the connection to Convex alive in the
+/// background. Its methods block the calling thread until the call completes, so they must not be
+/// used from within an async runtime.
+///
+/// Any other [`ConvexTransport`], such as a [`MockTransport`](crate::mock::MockTransport), can be
+/// wrapped with [`with_transport`](BlockingConvexClient::with_transport).
+pub struct BlockingConvexClient<T = ConvexClient>
+{
+    // Dropped before the runtime it was created on
+    client: T,
+    runtime: Runtime,
+}
+
+impl BlockingConvexClient
+{
+    /// Connects to a Convex deployment, e.g. `https://happy-animal-123.convex.cloud`.
+    ///
+    /// # Errors
+    /// Returns an error if the runtime cannot be started or the client cannot connect.
+    pub fn new(deployment_url: &str) -> Result<Self, ConvexCallError>
+    {
+        let runtime = new_runtime()?;
+        let client = runtime.block_on(ConvexClient::new(deployment_url))?;
+
+        Ok(Self { client, runtime })
+    }
+
+    /// Sets the auth token used by all following calls. Pass `None` to log out.
+    pub fn set_auth(&mut self, token: Option<String>)
+    {
+        self.runtime.block_on(self.client.set_auth(token));
+    }
+}
+
+impl<T: ConvexTransport + Send> BlockingConvexClient<T>
+{
+    /// Wraps an existing transport, running its calls on a runtime owned by the wrapper.
+    ///
+    /// # Errors
+    /// Returns an error if the runtime cannot be started.
+    pub fn with_transport(client: T) -> Result<Self, ConvexCallError>
+    {
+        Ok(Self {
+            client,
+            runtime: new_runtime()?,
+        })
+    }
+
+    /// Runs a query with generated arguments and decodes its result.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or the result does not match the generated return type.
+    pub fn query<A>(&mut self, args: A) -> Result<A::Output, ConvexCallError>
+    where
+        A: ConvexFunctionArgs<Kind = Query> + Send,
+    {
+        self.runtime.block_on(self.client.call_query(args))
+    }
+
+    /// Runs a mutation with generated arguments and decodes its result.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or the result does not match the generated return type.
+    pub fn mutation<A>(&mut self, args: A) -> Result<A::Output, ConvexCallError>
+    where
+        A: ConvexFunctionArgs<Kind = Mutation> + Send,
+    {
+        self.runtime.block_on(self.client.call_mutation(args))
+    }
+
+    /// Runs an action with generated arguments and decodes its result.
+    ///
+    /// # Errors
+    /// Returns an error if the call fails or the result does not match the generated return type.
+    pub fn action<A>(&mut self, args: A) -> Result<A::Output, ConvexCallError>
+    where
+        A: ConvexFunctionArgs<Kind = Action> + Send,
+    {
+        self.runtime.block_on(self.client.call_action(args))
+    }
+
+    /// Subscribes to a query, returning an iterator that blocks until the result changes.
+    ///
+    /// # Errors
+    /// Returns an error if the subscription cannot be created.
+    pub fn subscribe<A>(&mut self, args: A) -> Result<BlockingSubscription<'_, A, T::Subscription>, ConvexCallError>
+    where
+        A: ConvexFunctionArgs<Kind = Query> + Send,
+    {
+        let subscription = self.runtime.block_on(self.client.subscribe_query(args))?;
+
+        Ok(BlockingSubscription {
+            runtime: &self.runtime,
+            subscription,
+        })
+    }
+
+    /// The underlying async client, for calls not covered by this wrapper.
+    pub fn client(&mut self) -> &mut T
+    {
+        &mut self.client
+    }
+}
+
+/// The runtime backing a blocking client
+fn new_runtime() -> Result<Runtime, ConvexCallError>
+{
+    Builder::new_multi_thread()
+        .worker_threads(1)
+        .enable_all()
+        .build()
+        .map_err(|e| ConvexCallError::Client(e.into()))
+}
+
+/// A query subscription that blocks on each call to `next` until the result changes.
+///
+/// Created by [`BlockingConvexClient::subscribe`].
+pub struct BlockingSubscription<'a, A, S = convex::QuerySubscription>
+{
+    runtime: &'a Runtime,
+    subscription: TypedSubscription<A, S>,
+}
+
+impl<A, S> Iterator for BlockingSubscription<'_, A, S>
+where
+    A: ConvexFunctionArgs<Kind = Query>,
+    S: futures::Stream<Item = convex::FunctionResult> + Unpin,
+{
+    type Item = Result<A::Output, ConvexCallError>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        self.runtime.block_on(self.subscription.next())
+    }
+}
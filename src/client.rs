@@ -0,0 +1,368 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use convex::{ConvexClient, FunctionResult, QuerySubscription, Value as ConvexValue};
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::decode::from_function_result;
+use crate::errors::ConvexCallError;
+use crate::registry::FunctionType;
+use crate::validator::parse_argument_error_for;
+
+/// The kind of a Convex function, used to pick the client method that can call it.
+///
+/// Internal functions share the kind of their public counterpart, e.g. `internalQuery` is a
+/// [`Query`].
+pub trait FunctionKind
+{
+    /// The name of the kind, e.g. `"query"`.
+    const NAME: &'static str;
+
+    /// The kind as a value, for matching on it.
+    const TYPE: FunctionType;
+}
+
+/// Marker for functions defined with `query` or `internalQuery`.
+#[derive(Debug, Clone, Copy)]
+pub struct Query;
+
+/// Marker for functions defined with `mutation` or `internalMutation`.
+#[derive(Debug, Clone, Copy)]
+pub struct Mutation;
+
+/// Marker for functions defined with `action` or `internalAction`.
+#[derive(Debug, Clone, Copy)]
+pub struct Action;
+
+impl FunctionKind for Query
+{
+    const NAME: &'static str = "query";
+    const TYPE: FunctionType = FunctionType::Query;
+}
+
+impl FunctionKind for Mutation
+{
+    const NAME: &'static str = "mutation";
+    const TYPE: FunctionType = FunctionType::Mutation;
+}
+
+impl FunctionKind for Action
+{
+    const NAME: &'static str = "action";
+    const TYPE: FunctionType = FunctionType::Action;
+}
+
+/// Implemented by every generated `{Function}Args` struct.
+///
+/// Ties the arguments of a function to its path, its kind and the type it returns, so the typed
+/// methods of [`ConvexClientExt`](crate::convex::ConvexClientExt) can call it without any strings.
+pub trait ConvexFunctionArgs: Serialize
+{
+    /// The path of the function, e.g. `"games:getGame"`.
+    const PATH: &'static str;
+
+    /// Whether the function is a query, a mutation or an action.
+    type Kind: FunctionKind;
+
+    /// The type the function returns. Functions without a `returns` validator return
+    /// `serde_json::Value`.
+    type Output: DeserializeOwned;
+
+    /// The arguments whose generated Rust field is named differently, as `(argument, field)` pairs.
+    /// Used to point argument validation errors at the Rust field.
+    const FIELD_NAMES: &'static [(&'static str, &'static str)] = &[];
+}
+
+/// Implemented by every generated `{Function}Args` struct, naming the type of the data the function
+/// throws with `ConvexError`.
+///
+/// The error type is generated from the `throw new ConvexError(...)` expressions found in the
+/// handler. Functions that throw nothing recognizable use `serde_json::Value`.
+pub trait ConvexFunctionErrors: ConvexFunctionArgs
+{
+    /// The data thrown by the function.
+    type Error: DeserializeOwned;
+}
+
+/// Implemented by the generated `ConvexCall` enum, a call to any public function along with its
+/// arguments.
+///
+/// Lets components such as the outbox store calls to different functions in one queue and send
+/// them later.
+pub trait ConvexFunctionCall: Serialize + DeserializeOwned
+{
+    /// The path of the called function, e.g. `"messages:send"`.
+    fn path(&self) -> &'static str;
+
+    /// Sends the call through `transport` and returns the raw result value.
+    fn dispatch<T>(self, transport: &mut T) -> impl Future<Output = Result<ConvexValue, ConvexCallError>> + Send
+    where
+        T: ConvexTransport + Send;
+}
+
+/// Sends function calls to a Convex deployment.
+///
+/// Implemented for [`convex::ConvexClient`]. The typed helpers of
+/// [`ConvexClientExt`](crate::convex::ConvexClientExt) and components such as the outbox are written
+/// against this trait, so they can be driven by a stand-in in tests.
+pub trait ConvexTransport
+{
+    /// The stream of results returned by [`subscribe`](ConvexTransport::subscribe).
+    type Subscription: Stream<Item = FunctionResult> + Unpin + Send;
+
+    /// Subscribes to the query at `path`.
+    fn subscribe(
+        &mut self,
+        path: &str,
+        args: BTreeMap<String, ConvexValue>,
+    ) -> impl Future<Output = anyhow::Result<Self::Subscription>> + Send;
+
+    /// Runs the query at `path`.
+    fn query(
+        &mut self,
+        path: &str,
+        args: BTreeMap<String, ConvexValue>,
+    ) -> impl Future<Output = anyhow::Result<FunctionResult>> + Send;
+
+    /// Runs the mutation at `path`.
+    fn mutation(
+        &mut self,
+        path: &str,
+        args: BTreeMap<String, ConvexValue>,
+    ) -> impl Future<Output = anyhow::Result<FunctionResult>> + Send;
+
+    /// Runs the action at `path`.
+    fn action(
+        &mut self,
+        path: &str,
+        args: BTreeMap<String, ConvexValue>,
+    ) -> impl Future<Output = anyhow::Result<FunctionResult>> + Send;
+}
+
+impl ConvexTransport for ConvexClient
+{
+    type Subscription = QuerySubscription;
+
+    async fn subscribe(&mut self, path: &str, args: BTreeMap<String, ConvexValue>) -> anyhow::Result<QuerySubscription>
+    {
+        ConvexClient::subscribe(self, path, args).await
+    }
+
+    async fn query(&mut self, path: &str, args: BTreeMap<String, ConvexValue>) -> anyhow::Result<FunctionResult>
+    {
+        ConvexClient::query(self, path, args).await
+    }
+
+    async fn mutation(&mut self, path: &str, args: BTreeMap<String, ConvexValue>) -> anyhow::Result<FunctionResult>
+    {
+        ConvexClient::mutation(self, path, args).await
+    }
+
+    async fn action(&mut self, path: &str, args: BTreeMap<String, ConvexValue>) -> anyhow::Result<FunctionResult>
+    {
+        ConvexClient::action(self, path, args).await
+    }
+}
+
+/// A typed reference to a Convex function, like `api.messages.send` in TypeScript.
+///
+/// The generated `api` and `internal` modules hold one constant per function, e.g.
+/// `api::messages::send`, carrying the function's path along with its args, return and kind types.
+pub struct FunctionReference<Args, Return, Kind>
+{
+    path: &'static str,
+    _types: PhantomData<fn(Args) -> (Return, Kind)>,
+}
+
+impl<Args, Return, Kind> FunctionReference<Args, Return, Kind>
+{
+    /// Creates a reference to the function at `path`, e.g. `"messages:send"`.
+    pub const fn new(path: &'static str) -> Self
+    {
+        Self {
+            path,
+            _types: PhantomData,
+        }
+    }
+
+    /// The path of the function, e.g. `"messages:send"`.
+    pub const fn path(&self) -> &'static str
+    {
+        self.path
+    }
+}
+
+impl<Args, Return, Kind> Clone for FunctionReference<Args, Return, Kind>
+{
+    fn clone(&self) -> Self
+    {
+        *self
+    }
+}
+
+impl<Args, Return, Kind> Copy for FunctionReference<Args, Return, Kind> {}
+
+impl<Args, Return, Kind> std::fmt::Debug for FunctionReference<Args, Return, Kind>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_tuple("FunctionReference").field(&self.path).finish()
+    }
+}
+
+/// A query subscription that yields results decoded into the query's generated return type.
+///
+/// Convex re-sends a query's result whenever any subscribed query changes, so consecutive results
+/// that are identical are skipped. Created by
+/// [`ConvexClientExt::subscribe_query`](crate::convex::ConvexClientExt::subscribe_query).
+pub struct TypedSubscription<A, S = QuerySubscription>
+{
+    inner: S,
+    last: Option<FunctionResult>,
+    _args: PhantomData<fn() -> A>,
+}
+
+impl<A, S> TypedSubscription<A, S>
+where
+    A: ConvexFunctionArgs<Kind = Query>,
+    S: Stream<Item = FunctionResult> + Unpin,
+{
+    /// Wraps a subscription created with the raw client, or any other stream of results.
+    ///
+    /// The results must come from the query identified by `A`, otherwise they will fail to
+    /// decode.
+    pub fn new(inner: S) -> Self
+    {
+        Self {
+            inner,
+            last: None,
+            _args: PhantomData,
+        }
+    }
+
+    /// The underlying untyped subscription.
+    pub fn inner(&self) -> &S
+    {
+        &self.inner
+    }
+
+    /// Poll for the next result that differs from the previous one, without decoding it
+    fn poll_changed(&mut self, cx: &mut Context<'_>) -> Poll<Option<FunctionResult>>
+    {
+        loop {
+            match self.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(result)) if self.last.as_ref() == Some(&result) => continue,
+                Poll::Ready(Some(result)) => {
+                    self.last = Some(result.clone());
+                    return Poll::Ready(Some(result));
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<A, S> Stream for TypedSubscription<A, S>
+where
+    A: ConvexFunctionArgs<Kind = Query>,
+    S: Stream<Item = FunctionResult> + Unpin,
+{
+    type Item = Result<A::Output, ConvexCallError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>
+    {
+        self.get_mut().poll_changed(cx).map(|result| result.map(decode_result::<A>))
+    }
+}
+
+/// Combines several subscriptions to the same query into one stream of snapshots.
+///
+/// Once every subscription has produced a result, each change to any of them yields the latest
+/// result of all of them, in the order the subscriptions were given. The stream ends when any of
+/// the subscriptions ends, or immediately if there are none.
+pub struct WatchMany<A, S = QuerySubscription>
+{
+    subscriptions: Vec<TypedSubscription<A, S>>,
+    latest: Vec<Option<FunctionResult>>,
+}
+
+/// Watches several subscriptions to the same query at once. See [`WatchMany`].
+pub fn watch_many<A, S>(subscriptions: impl IntoIterator<Item = TypedSubscription<A, S>>) -> WatchMany<A, S>
+where
+    A: ConvexFunctionArgs<Kind = Query>,
+    S: Stream<Item = FunctionResult> + Unpin,
+{
+    let subscriptions = subscriptions.into_iter().collect::<Vec<_>>();
+    let latest = vec![None; subscriptions.len()];
+
+    WatchMany { subscriptions, latest }
+}
+
+impl<A, S> Stream for WatchMany<A, S>
+where
+    A: ConvexFunctionArgs<Kind = Query>,
+    S: Stream<Item = FunctionResult> + Unpin,
+{
+    type Item = Vec<Result<A::Output, ConvexCallError>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>
+    {
+        let this = self.get_mut();
+        let mut changed = false;
+
+        if this.subscriptions.is_empty() {
+            return Poll::Ready(None);
+        }
+
+        // Drain every subscription so all of them register for wake-ups
+        for (subscription, latest) in this.subscriptions.iter_mut().zip(this.latest.iter_mut()) {
+            loop {
+                match subscription.poll_changed(cx) {
+                    Poll::Ready(Some(result)) => {
+                        *latest = Some(result);
+                        changed = true;
+                    }
+                    Poll::Ready(None) => return Poll::Ready(None),
+                    Poll::Pending => break,
+                }
+            }
+        }
+
+        if !changed || this.latest.iter().any(Option::is_none) {
+            return Poll::Pending;
+        }
+
+        Poll::Ready(Some(
+            this.latest
+                .iter()
+                .flatten()
+                .map(|result| decode_result::<A>(result.clone()))
+                .collect(),
+        ))
+    }
+}
+
+/// Decode the result of a call made with the arguments `A` into its generated return type
+pub(crate) fn decode_result<A: ConvexFunctionArgs>(result: FunctionResult) -> Result<A::Output, ConvexCallError>
+{
+    from_function_result(result).map_err(|error| argument_error::<A>(error.into()))
+}
+
+/// Replace the message of a call Convex rejected for its arguments with the parsed error, pointing
+/// at the generated field of `A`
+pub(crate) fn argument_error<A: ConvexFunctionArgs>(error: ConvexCallError) -> ConvexCallError
+{
+    match error {
+        ConvexCallError::FunctionFailed(message) => match parse_argument_error_for::<A>(&message) {
+            Some(error) => ConvexCallError::InvalidArguments(Box::new(error)),
+            None => ConvexCallError::FunctionFailed(message),
+        },
+        other => other,
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use convex::Value as ConvexValue;
+use convex_typegen_codegen::convex::{describe_data_type, literal_value};
+use serde_json::Value as JsonValue;
+
+use crate::convex::ConvexFunction;
+use crate::errors::ConvexCoercionError;
+use crate::json::{decode_wrapper, from_convex_json};
+
+/// Converts the JSON arguments of a function call into Convex values, guided by the function's
+/// parsed `args` validators.
+///
+/// Unlike [`IntoConvexValue`](crate::convex::IntoConvexValue), numbers are not guessed: a
+/// `v.number()` argument always becomes `Float64` and a `v.int64()` argument always becomes
+/// `Int64`. See [`coerce_value`] for the full set of conversions.
+///
+/// # Errors
+/// Returns an error naming the offending argument if a required argument is missing, an unknown
+/// argument is present, or a value does not fit its validator.
+pub fn coerce_args(
+    function: &ConvexFunction,
+    args: BTreeMap<String, JsonValue>,
+) -> Result<BTreeMap<String, ConvexValue>, ConvexCoercionError>
+{
+    let properties = function
+        .params
+        .iter()
+        .map(|param| (param.name.as_str(), &param.data_type))
+        .collect::<Vec<_>>();
+
+    let mut path = Vec::new();
+    coerce_fields(&properties, args.into_iter().collect(), &mut path)
+}
+
+/// Converts a JSON value into a Convex value, guided by a parsed data type.
+///
+/// * `v.number()` accepts any JSON number and produces `Float64`
+/// * `v.int64()` accepts integral numbers, decimal strings and `{"$integer": ...}`
+/// * `v.bytes()` accepts base64 strings, arrays of byte values and `{"$bytes": ...}`
+/// * `v.any()` reads the value as Convex JSON, so plain numbers become `Float64`
+/// * `null` for a field of an object whose type is `v.optional(...)` is treated as a missing field
+///
+/// # Errors
+/// Returns an error with the path to the offending value if the JSON does not fit the type.
+pub fn coerce_value(json: JsonValue, data_type: &JsonValue) -> Result<ConvexValue, ConvexCoercionError>
+{
+    let mut path = Vec::new();
+    coerce(json, data_type, &mut path)
+}
+
+fn coerce(json: JsonValue, data_type: &JsonValue, path: &mut Vec<String>) -> Result<ConvexValue, ConvexCoercionError>
+{
+    let mismatch = |json: &JsonValue, path: &Vec<String>| ConvexCoercionError {
+        path: path.clone(),
+        expected: describe_data_type(data_type),
+        found: json_kind(json).to_string(),
+    };
+
+    match data_type["type"].as_str().unwrap_or("any") {
+        "string" | "id" => match json {
+            JsonValue::String(s) => Ok(ConvexValue::String(s)),
+            other => Err(mismatch(&other, path)),
+        },
+        "boolean" => match json {
+            JsonValue::Bool(b) => Ok(ConvexValue::Boolean(b)),
+            other => Err(mismatch(&other, path)),
+        },
+        "null" => match json {
+            JsonValue::Null => Ok(ConvexValue::Null),
+            other => Err(mismatch(&other, path)),
+        },
+        "number" => match &json {
+            JsonValue::Number(n) => n.as_f64().map(ConvexValue::Float64).ok_or_else(|| mismatch(&json, path)),
+            JsonValue::Object(map) => match decode_wrapper(map) {
+                Some(Ok(ConvexValue::Float64(f))) => Ok(ConvexValue::Float64(f)),
+                _ => Err(mismatch(&json, path)),
+            },
+            _ => Err(mismatch(&json, path)),
+        },
+        "int64" => json_to_int64(&json)
+            .map(ConvexValue::Int64)
+            .ok_or_else(|| mismatch(&json, path)),
+        "bytes" => json_to_bytes(&json)
+            .map(ConvexValue::Bytes)
+            .ok_or_else(|| mismatch(&json, path)),
+        "literal" => {
+            let expected = literal_value(data_type).ok_or_else(|| mismatch(&json, path))?;
+            let value = match (&expected, &json) {
+                (ConvexValue::Int64(_), _) => json_to_int64(&json).map(ConvexValue::Int64),
+                (ConvexValue::Float64(_), JsonValue::Number(n)) => n.as_f64().map(ConvexValue::Float64),
+                (ConvexValue::String(_), JsonValue::String(s)) => Some(ConvexValue::String(s.clone())),
+                (ConvexValue::Boolean(_), JsonValue::Bool(b)) => Some(ConvexValue::Boolean(*b)),
+                _ => None,
+            };
+            match value {
+                Some(value) if value == expected => Ok(value),
+                _ => Err(mismatch(&json, path)),
+            }
+        }
+        "optional" => coerce(json, &data_type["inner"], path),
+        "array" => match json {
+            JsonValue::Array(arr) => {
+                let mut values = Vec::with_capacity(arr.len());
+                for (i, item) in arr.into_iter().enumerate() {
+                    path.push(i.to_string());
+                    values.push(coerce(item, &data_type["elements"], path)?);
+                    path.pop();
+                }
+                Ok(ConvexValue::Array(values))
+            }
+            other => Err(mismatch(&other, path)),
+        },
+        "object" => match json {
+            JsonValue::Object(map) => {
+                let properties = data_type["properties"]
+                    .as_object()
+                    .map(|props| props.iter().map(|(k, v)| (k.as_str(), v)).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                coerce_fields(&properties, map.into_iter().collect(), path).map(ConvexValue::Object)
+            }
+            other => Err(mismatch(&other, path)),
+        },
+        "record" => match json {
+            JsonValue::Object(map) => {
+                let mut fields = BTreeMap::new();
+                for (key, value) in map {
+                    path.push(key.clone());
+                    coerce(JsonValue::String(key.clone()), &data_type["keyType"], path)?;
+                    fields.insert(key, coerce(value, &data_type["valueType"], path)?);
+                    path.pop();
+                }
+                Ok(ConvexValue::Object(fields))
+            }
+            other => Err(mismatch(&other, path)),
+        },
+        "union" => {
+            let variants = data_type["variants"].as_array().map(Vec::as_slice).unwrap_or_default();
+            variants
+                .iter()
+                .find_map(|variant| coerce(json.clone(), variant, &mut path.clone()).ok())
+                .ok_or_else(|| mismatch(&json, path))
+        }
+        _ => from_convex_json(json.clone()).map_err(|_| mismatch(&json, path)),
+    }
+}
+
+/// Coerce the fields of an object against its property types
+fn coerce_fields(
+    properties: &[(&str, &JsonValue)],
+    mut fields: BTreeMap<String, JsonValue>,
+    path: &mut Vec<String>,
+) -> Result<BTreeMap<String, ConvexValue>, ConvexCoercionError>
+{
+    let mut values = BTreeMap::new();
+
+    for (name, prop_type) in properties {
+        let optional = prop_type["type"].as_str() == Some("optional");
+        path.push(name.to_string());
+
+        match fields.remove(*name) {
+            // JSON has no `undefined`, so a null optional field is read as a missing one
+            Some(JsonValue::Null) if optional && prop_type["inner"]["type"].as_str() != Some("null") => {}
+            Some(json) => {
+                values.insert(name.to_string(), coerce(json, prop_type, path)?);
+            }
+            None if optional => {}
+            None => {
+                return Err(ConvexCoercionError {
+                    path: path.clone(),
+                    expected: describe_data_type(prop_type),
+                    found: "missing field".to_string(),
+                });
+            }
+        }
+
+        path.pop();
+    }
+
+    if let Some(unknown) = fields.into_keys().next() {
+        path.push(unknown);
+        return Err(ConvexCoercionError {
+            path: path.clone(),
+            expected: "no field".to_string(),
+            found: "unknown field".to_string(),
+        });
+    }
+
+    Ok(values)
+}
+
+/// Read an int64 from an integral number, a decimal string or an `$integer` wrapper
+fn json_to_int64(json: &JsonValue) -> Option<i64>
+{
+    match json {
+        JsonValue::Number(n) => n.as_i64().or_else(|| {
+            n.as_f64()
+                .filter(|f| f.fract() == 0.0 && f.abs() < i64::MAX as f64)
+                .map(|f| f as i64)
+        }),
+        JsonValue::String(s) => s.parse().ok(),
+        JsonValue::Object(map) => match decode_wrapper(map) {
+            Some(Ok(ConvexValue::Int64(i))) => Some(i),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Read bytes from a base64 string, an array of byte values or a `$bytes` wrapper
+fn json_to_bytes(json: &JsonValue) -> Option<Vec<u8>>
+{
+    match json {
+        JsonValue::String(s) => STANDARD.decode(s).ok(),
+        JsonValue::Array(arr) => arr.iter().map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok())).collect(),
+        JsonValue::Object(map) => match decode_wrapper(map) {
+            Some(Ok(ConvexValue::Bytes(b))) => Some(b),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// A short description of a JSON value's type for error messages
+fn json_kind(json: &JsonValue) -> &'static str
+{
+    match json {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "array",
+        JsonValue::Object(_) => "object",
+    }
+}
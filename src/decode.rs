@@ -0,0 +1,347 @@
+use std::collections::btree_map;
+use std::vec;
+
+use convex::{FunctionResult, Value as ConvexValue};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Deserializer, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::errors::ConvexDecodeError;
+
+/// Decodes a Convex value into any type implementing `Deserialize`.
+///
+/// Generated table and return types can be decoded directly from the values returned by the
+/// Convex client. `Int64` and `Float64` values are accepted by any numeric target as long as the
+/// number fits, `Bytes` decode into `Vec<u8>` and objects decode into structs and maps.
+///
+/// # Errors
+/// Returns an error if the value does not match the shape of `T`. The error carries the path to the
+/// offending field.
+pub fn from_value<T: DeserializeOwned>(value: ConvexValue) -> Result<T, ConvexDecodeError>
+{
+    T::deserialize(ValueDeserializer::new(value))
+}
+
+/// Decodes the result of a Convex function call into any type implementing `Deserialize`.
+///
+/// # Errors
+/// Returns an error if the function failed, or if the returned value does not match the shape of
+/// `T`.
+pub fn from_function_result<T: DeserializeOwned>(result: FunctionResult) -> Result<T, ConvexDecodeError>
+{
+    match result {
+        FunctionResult::Value(value) => from_value(value),
+        FunctionResult::ErrorMessage(message) => Err(ConvexDecodeError::FunctionFailed(message)),
+        FunctionResult::ConvexError(error) => Err(ConvexDecodeError::ApplicationError(error)),
+    }
+}
+
+/// A serde `Deserializer` that reads from an owned `convex::Value`.
+pub struct ValueDeserializer
+{
+    value: ConvexValue,
+}
+
+impl ValueDeserializer
+{
+    /// Creates a deserializer for the given Convex value.
+    pub fn new(value: ConvexValue) -> Self
+    {
+        Self { value }
+    }
+
+    /// Convex numbers are always `f64`, so integer targets accept floats without a fractional part.
+    fn deserialize_integer<'de, V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ConvexDecodeError>
+    {
+        match self.value {
+            ConvexValue::Float64(f) if f.fract() == 0.0 && f >= i64::MIN as f64 && f < i64::MAX as f64 => {
+                visitor.visit_i64(f as i64)
+            }
+            ConvexValue::Float64(f) if f.fract() == 0.0 && f >= 0.0 && f < u64::MAX as f64 => visitor.visit_u64(f as u64),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer
+{
+    type Error = ConvexDecodeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        match self.value {
+            ConvexValue::Null => visitor.visit_unit(),
+            ConvexValue::Int64(i) => visitor.visit_i64(i),
+            ConvexValue::Float64(f) => visitor.visit_f64(f),
+            ConvexValue::Boolean(b) => visitor.visit_bool(b),
+            ConvexValue::String(s) => visitor.visit_string(s),
+            ConvexValue::Bytes(b) => visitor.visit_byte_buf(b),
+            ConvexValue::Array(arr) => visitor.visit_seq(SeqAccess::new(arr)),
+            ConvexValue::Object(map) => visitor.visit_map(MapAccess::new(map)),
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        match self.value {
+            ConvexValue::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        match self.value {
+            // `Vec<u8>` asks for a sequence, so expose the bytes one element at a time
+            ConvexValue::Bytes(bytes) => visitor.visit_seq(SeqAccess::new(
+                bytes.into_iter().map(|b| ConvexValue::Int64(b as i64)).collect(),
+            )),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    {
+        match self.value {
+            ConvexValue::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            ConvexValue::Object(map) if map.len() == 1 => {
+                let (variant, value) = map.into_iter().next().expect("map has exactly one entry");
+                visitor.visit_enum(EnumAccess { variant, value })
+            }
+            other => Err(de::Error::custom(format!(
+                "expected a string or single-key object for an enum, found {}",
+                value_kind(&other)
+            ))),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool f32 f64 char str string bytes byte_buf unit unit_struct tuple tuple_struct map struct identifier
+        i128 u128
+    }
+}
+
+struct SeqAccess
+{
+    iter: vec::IntoIter<ConvexValue>,
+    index: usize,
+}
+
+impl SeqAccess
+{
+    fn new(values: Vec<ConvexValue>) -> Self
+    {
+        Self {
+            iter: values.into_iter(),
+            index: 0,
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess
+{
+    type Error = ConvexDecodeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    {
+        match self.iter.next() {
+            Some(value) => {
+                let index = self.index;
+                self.index += 1;
+                seed.deserialize(ValueDeserializer::new(value))
+                    .map(Some)
+                    .map_err(|e| e.with_path_segment(index.to_string()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize>
+    {
+        Some(self.iter.len())
+    }
+}
+
+struct MapAccess
+{
+    iter: btree_map::IntoIter<String, ConvexValue>,
+    /// The key and value of the entry whose key was just handed out
+    pending: Option<(String, ConvexValue)>,
+}
+
+impl MapAccess
+{
+    fn new(map: std::collections::BTreeMap<String, ConvexValue>) -> Self
+    {
+        Self {
+            iter: map.into_iter(),
+            pending: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess
+{
+    type Error = ConvexDecodeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                let decoded = seed.deserialize(key.clone().into_deserializer())?;
+                self.pending = Some((key, value));
+                Ok(Some(decoded))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    {
+        let (key, value) = self
+            .pending
+            .take()
+            .ok_or_else(|| <ConvexDecodeError as de::Error>::custom("value requested before key"))?;
+        seed.deserialize(ValueDeserializer::new(value))
+            .map_err(|e| e.with_path_segment(key))
+    }
+
+    fn size_hint(&self) -> Option<usize>
+    {
+        Some(self.iter.len())
+    }
+}
+
+struct EnumAccess
+{
+    variant: String,
+    value: ConvexValue,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess
+{
+    type Error = ConvexDecodeError;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    {
+        let variant = seed.deserialize(self.variant.clone().into_deserializer())?;
+        Ok((
+            variant,
+            VariantAccess {
+                name: self.variant,
+                value: self.value,
+            },
+        ))
+    }
+}
+
+struct VariantAccess
+{
+    name: String,
+    value: ConvexValue,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess
+{
+    type Error = ConvexDecodeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error>
+    {
+        match self.value {
+            ConvexValue::Null => Ok(()),
+            other => Err(<ConvexDecodeError as de::Error>::custom(format!(
+                "expected null for unit variant, found {}",
+                value_kind(&other)
+            ))
+            .with_path_segment(self.name)),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Self::Error>
+    {
+        seed.deserialize(ValueDeserializer::new(self.value))
+            .map_err(|e| e.with_path_segment(self.name))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    {
+        Deserializer::deserialize_seq(ValueDeserializer::new(self.value), visitor)
+            .map_err(|e| e.with_path_segment(self.name))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+    {
+        Deserializer::deserialize_map(ValueDeserializer::new(self.value), visitor)
+            .map_err(|e| e.with_path_segment(self.name))
+    }
+}
+
+/// A short description of a value's type for error messages
+pub(crate) fn value_kind(value: &ConvexValue) -> &'static str
+{
+    match value {
+        ConvexValue::Null => "null",
+        ConvexValue::Int64(_) => "int64",
+        ConvexValue::Float64(_) => "float64",
+        ConvexValue::Boolean(_) => "boolean",
+        ConvexValue::String(_) => "string",
+        ConvexValue::Bytes(_) => "bytes",
+        ConvexValue::Array(_) => "array",
+        ConvexValue::Object(_) => "object",
+    }
+}
@@ -0,0 +1,612 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use convex::{FunctionResult, Value as ConvexValue};
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::client::{Action, ConvexFunctionArgs, ConvexTransport, FunctionKind, Mutation, Query};
+use crate::convex::ConvexSchema;
+use crate::decode::from_value;
+use crate::errors::{ConvexEmulatorError, ConvexTypeGeneratorError};
+use crate::limits::check_document;
+use crate::mock::encode_output;
+use crate::registry::FunctionType;
+use crate::validator::Validator;
+
+/// A stored document, including its `_id` and `_creationTime` system fields.
+pub type Document = BTreeMap<String, ConvexValue>;
+
+/// The index every table has, ordering documents by when they were inserted
+pub const BY_CREATION_TIME: &str = "by_creation_time";
+
+/// The index every table has, ordering documents by id
+pub const BY_ID: &str = "by_id";
+
+#[derive(Debug, Clone)]
+struct Table
+{
+    validator: Validator,
+    indexes: BTreeMap<String, Vec<String>>,
+    documents: BTreeMap<String, Document>,
+}
+
+/// An in-memory document store that behaves like a Convex deployment's database.
+///
+/// Tables, their validators and their indexes come from a parsed [`ConvexSchema`]. Writes are
+/// checked against the table's validator and Convex's limits, `v.id(...)` fields must point into the
+/// right table, and index reads return documents in Convex's value order.
+#[derive(Debug, Clone)]
+pub struct Database
+{
+    tables: BTreeMap<String, Table>,
+    // Every id ever issued, with its table, so ids of deleted documents still validate
+    id_tables: HashMap<String, String>,
+    next_id: u64,
+    last_creation_time: f64,
+}
+
+impl Database
+{
+    /// Creates an empty database with the tables of `schema`.
+    ///
+    /// # Errors
+    /// Returns an error if a column type is invalid.
+    pub fn new(schema: &ConvexSchema) -> Result<Self, ConvexTypeGeneratorError>
+    {
+        let tables = schema
+            .tables
+            .iter()
+            .map(|table| {
+                let mut indexes = table
+                    .indexes
+                    .iter()
+                    .map(|index| (index.name.clone(), index.fields.clone()))
+                    .collect::<BTreeMap<_, _>>();
+                indexes.insert(BY_CREATION_TIME.to_string(), Vec::new());
+                indexes.insert(BY_ID.to_string(), vec!["_id".to_string()]);
+
+                let table_state = Table {
+                    validator: Validator::for_table(table)?,
+                    indexes,
+                    documents: BTreeMap::new(),
+                };
+                Ok((table.name.clone(), table_state))
+            })
+            .collect::<Result<_, ConvexTypeGeneratorError>>()?;
+
+        Ok(Self {
+            tables,
+            id_tables: HashMap::new(),
+            next_id: 1,
+            last_creation_time: 0.0,
+        })
+    }
+
+    /// Inserts a document and returns its new id.
+    ///
+    /// # Errors
+    /// Returns an error if the table is unknown or the document is invalid for it.
+    pub fn insert(&mut self, table: &str, fields: Document) -> Result<String, ConvexEmulatorError>
+    {
+        self.check(table, &fields)?;
+
+        let id = format!("{}{}", self.next_id, table);
+        self.next_id += 1;
+
+        // Creation times are milliseconds since the epoch, and strictly increasing
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs_f64() * 1000.0)
+            .unwrap_or_default();
+        self.last_creation_time = now.max(self.last_creation_time + 1.0);
+
+        let mut document = fields;
+        document.insert("_id".to_string(), ConvexValue::String(id.clone()));
+        document.insert("_creationTime".to_string(), ConvexValue::Float64(self.last_creation_time));
+
+        self.id_tables.insert(id.clone(), table.to_string());
+        self.table_mut(table)?.documents.insert(id.clone(), document);
+
+        Ok(id)
+    }
+
+    /// The document with the given id, if it exists.
+    pub fn get(&self, id: &str) -> Option<&Document>
+    {
+        let table = self.id_tables.get(id)?;
+        self.tables.get(table)?.documents.get(id)
+    }
+
+    /// Sets the given fields of a document, keeping its other fields.
+    ///
+    /// Fields can't be removed by a patch. Use [`replace`](Database::replace) instead.
+    ///
+    /// # Errors
+    /// Returns an error if the document does not exist or the result is invalid for its table.
+    pub fn patch(&mut self, id: &str, fields: Document) -> Result<(), ConvexEmulatorError>
+    {
+        let mut merged = user_fields(
+            self.get(id)
+                .ok_or_else(|| ConvexEmulatorError::DocumentNotFound(id.to_string()))?,
+        );
+        merged.extend(fields);
+        self.replace(id, merged)
+    }
+
+    /// Replaces the fields of a document, keeping its system fields.
+    ///
+    /// # Errors
+    /// Returns an error if the document does not exist or the new fields are invalid for its table.
+    pub fn replace(&mut self, id: &str, fields: Document) -> Result<(), ConvexEmulatorError>
+    {
+        let table = self
+            .id_tables
+            .get(id)
+            .cloned()
+            .ok_or_else(|| ConvexEmulatorError::DocumentNotFound(id.to_string()))?;
+        self.check(&table, &fields)?;
+
+        let document = self
+            .table_mut(&table)?
+            .documents
+            .get_mut(id)
+            .ok_or_else(|| ConvexEmulatorError::DocumentNotFound(id.to_string()))?;
+        document.retain(|name, _| name.starts_with('_'));
+        document.extend(fields);
+
+        Ok(())
+    }
+
+    /// Deletes a document and returns it.
+    ///
+    /// # Errors
+    /// Returns an error if the document does not exist.
+    pub fn delete(&mut self, id: &str) -> Result<Document, ConvexEmulatorError>
+    {
+        let table = self
+            .id_tables
+            .get(id)
+            .cloned()
+            .ok_or_else(|| ConvexEmulatorError::DocumentNotFound(id.to_string()))?;
+        self.table_mut(&table)?
+            .documents
+            .remove(id)
+            .ok_or_else(|| ConvexEmulatorError::DocumentNotFound(id.to_string()))
+    }
+
+    /// Every document in a table, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error if the table is unknown.
+    pub fn scan(&self, table: &str) -> Result<Vec<&Document>, ConvexEmulatorError>
+    {
+        self.query_index(table, BY_CREATION_TIME, &IndexRange::new())
+    }
+
+    /// The documents of a table within a range of one of its indexes, in index order.
+    ///
+    /// Documents are ordered by the index fields, then by `_creationTime`, comparing values the way
+    /// Convex does. A missing field sorts before every value. Reverse the result for descending order.
+    ///
+    /// # Errors
+    /// Returns an error if the table or index is unknown, or the range does not follow the index.
+    pub fn query_index(&self, table: &str, index: &str, range: &IndexRange) -> Result<Vec<&Document>, ConvexEmulatorError>
+    {
+        let table_state = self
+            .tables
+            .get(table)
+            .ok_or_else(|| ConvexEmulatorError::UnknownTable(table.to_string()))?;
+        let fields = table_state
+            .indexes
+            .get(index)
+            .ok_or_else(|| ConvexEmulatorError::UnknownIndex {
+                table: table.to_string(),
+                index: index.to_string(),
+            })?;
+
+        // Every index ends with the creation time, then the id
+        let mut key_fields = fields.iter().map(String::as_str).collect::<Vec<_>>();
+        key_fields.extend(["_creationTime", "_id"]);
+        range.check(index, &key_fields)?;
+
+        let mut documents = table_state
+            .documents
+            .values()
+            .map(|document| (index_key(document, &key_fields), document))
+            .filter(|(key, _)| range.contains(key))
+            .collect::<Vec<_>>();
+        documents.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(documents.into_iter().map(|(_, document)| document).collect())
+    }
+
+    /// Check new fields for a table against its validator and Convex's limits
+    fn check(&self, table: &str, fields: &Document) -> Result<(), ConvexEmulatorError>
+    {
+        let table_state = self
+            .tables
+            .get(table)
+            .ok_or_else(|| ConvexEmulatorError::UnknownTable(table.to_string()))?;
+
+        let value = ConvexValue::Object(fields.clone());
+        check_document(&value).map_err(ConvexEmulatorError::LimitViolation)?;
+        table_state
+            .validator
+            .validate_with_ids(&value, |id| self.id_tables.get(id).cloned())
+            .map_err(|error| ConvexEmulatorError::InvalidDocument {
+                table: table.to_string(),
+                error,
+            })
+    }
+
+    fn table_mut(&mut self, table: &str) -> Result<&mut Table, ConvexEmulatorError>
+    {
+        self.tables
+            .get_mut(table)
+            .ok_or_else(|| ConvexEmulatorError::UnknownTable(table.to_string()))
+    }
+}
+
+/// A range of an index to read, built like Convex's index range expressions.
+///
+/// Equality conditions must cover a prefix of the index fields, in order. They can be followed by a
+/// lower and an upper bound on the next field.
+///
+/// ```
+/// use convex_typegen::emulator::IndexRange;
+///
+/// // q.eq("channel", "general").gte("sentAt", 100).lt("sentAt", 200)
+/// let range = IndexRange::new()
+///     .eq("channel", "general")
+///     .gte("sentAt", 100.0)
+///     .lt("sentAt", 200.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IndexRange
+{
+    equal: Vec<(String, ConvexValue)>,
+    lower: Option<Bound>,
+    upper: Option<Bound>,
+}
+
+#[derive(Debug, Clone)]
+struct Bound
+{
+    field: String,
+    value: ConvexValue,
+    inclusive: bool,
+}
+
+impl IndexRange
+{
+    /// A range covering the whole index.
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    /// Only documents whose `field` equals `value`.
+    pub fn eq(mut self, field: &str, value: impl Into<ConvexValue>) -> Self
+    {
+        self.equal.push((field.to_string(), value.into()));
+        self
+    }
+
+    /// Only documents whose `field` is greater than `value`.
+    pub fn gt(self, field: &str, value: impl Into<ConvexValue>) -> Self
+    {
+        self.lower_bound(field, value.into(), false)
+    }
+
+    /// Only documents whose `field` is greater than or equal to `value`.
+    pub fn gte(self, field: &str, value: impl Into<ConvexValue>) -> Self
+    {
+        self.lower_bound(field, value.into(), true)
+    }
+
+    /// Only documents whose `field` is less than `value`.
+    pub fn lt(self, field: &str, value: impl Into<ConvexValue>) -> Self
+    {
+        self.upper_bound(field, value.into(), false)
+    }
+
+    /// Only documents whose `field` is less than or equal to `value`.
+    pub fn lte(self, field: &str, value: impl Into<ConvexValue>) -> Self
+    {
+        self.upper_bound(field, value.into(), true)
+    }
+
+    fn lower_bound(mut self, field: &str, value: ConvexValue, inclusive: bool) -> Self
+    {
+        self.lower = Some(Bound {
+            field: field.to_string(),
+            value,
+            inclusive,
+        });
+        self
+    }
+
+    fn upper_bound(mut self, field: &str, value: ConvexValue, inclusive: bool) -> Self
+    {
+        self.upper = Some(Bound {
+            field: field.to_string(),
+            value,
+            inclusive,
+        });
+        self
+    }
+
+    /// Check that the range follows the fields of the index
+    fn check(&self, index: &str, key_fields: &[&str]) -> Result<(), ConvexEmulatorError>
+    {
+        let invalid = |details: String| ConvexEmulatorError::InvalidIndexRange {
+            index: index.to_string(),
+            details,
+        };
+
+        for (position, (field, _)) in self.equal.iter().enumerate() {
+            if key_fields.get(position) != Some(&field.as_str()) {
+                return Err(invalid(format!(
+                    "'{}' is compared for equality where '{}' was expected",
+                    field,
+                    key_fields.get(position).unwrap_or(&"nothing")
+                )));
+            }
+        }
+
+        let next = key_fields.get(self.equal.len()).copied();
+        for bound in self.lower.iter().chain(&self.upper) {
+            if Some(bound.field.as_str()) != next {
+                return Err(invalid(format!(
+                    "'{}' is bounded where '{}' was expected",
+                    bound.field,
+                    next.unwrap_or("nothing")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn contains(&self, key: &[Option<&ConvexValue>]) -> bool
+    {
+        let equal = self.equal.iter().zip(key).all(|((_, value), field)| *field == Some(value));
+        let position = self.equal.len();
+        let field = key.get(position).copied().flatten();
+        let lower = self.lower.as_ref().is_none_or(|bound| match field.cmp(&Some(&bound.value)) {
+            Ordering::Greater => true,
+            Ordering::Equal => bound.inclusive,
+            Ordering::Less => false,
+        });
+        let upper = self.upper.as_ref().is_none_or(|bound| match field.cmp(&Some(&bound.value)) {
+            Ordering::Less => true,
+            Ordering::Equal => bound.inclusive,
+            Ordering::Greater => false,
+        });
+
+        equal && lower && upper
+    }
+}
+
+/// The values of a document for the fields of an index, `None` for missing fields
+fn index_key<'a>(document: &'a Document, key_fields: &[&str]) -> Vec<Option<&'a ConvexValue>>
+{
+    key_fields
+        .iter()
+        .map(|field| {
+            let mut segments = field.split('.');
+            let first = document.get(segments.next()?)?;
+            segments.try_fold(first, |value, segment| match value {
+                ConvexValue::Object(fields) => fields.get(segment),
+                _ => None,
+            })
+        })
+        .collect()
+}
+
+fn user_fields(document: &Document) -> Document
+{
+    document
+        .iter()
+        .filter(|(name, _)| !name.starts_with('_'))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+type Handler = Box<dyn FnMut(&mut Database, BTreeMap<String, ConvexValue>) -> FunctionResult + Send>;
+
+struct Function
+{
+    kind: FunctionType,
+    handler: Handler,
+}
+
+struct Subscription
+{
+    path: String,
+    args: BTreeMap<String, ConvexValue>,
+    last: FunctionResult,
+    sender: UnboundedSender<FunctionResult>,
+}
+
+/// A local Convex deployment for integration tests, backed by a [`Database`].
+///
+/// Each function is implemented in Rust by a handler registered with
+/// [`on_query`](Emulator::on_query), [`on_mutation`](Emulator::on_mutation) or
+/// [`on_action`](Emulator::on_action). The emulator is a [`ConvexTransport`], so code written
+/// against [`ConvexClientExt`](crate::convex::ConvexClientExt) runs against it unchanged:
+///
+/// * Mutations are transactional. A mutation that fails leaves the database untouched.
+/// * Subscriptions are reactive. After every mutation or action, subscribed queries are run again
+///   and their subscribers receive the new result if it changed.
+pub struct Emulator
+{
+    database: Database,
+    functions: HashMap<String, Function>,
+    subscriptions: Vec<Subscription>,
+}
+
+impl Emulator
+{
+    /// Creates an emulator with an empty database for `schema` and no functions.
+    ///
+    /// # Errors
+    /// Returns an error if a column type is invalid.
+    pub fn new(schema: &ConvexSchema) -> Result<Self, ConvexTypeGeneratorError>
+    {
+        Ok(Self {
+            database: Database::new(schema)?,
+            functions: HashMap::new(),
+            subscriptions: Vec::new(),
+        })
+    }
+
+    /// The database, for seeding and inspecting data in tests.
+    ///
+    /// Changes made directly to the database are not pushed to subscribers.
+    pub fn database(&mut self) -> &mut Database
+    {
+        &mut self.database
+    }
+
+    /// Implements the query identified by `A`. Queries can only read the database.
+    pub fn on_query<A, F>(&mut self, mut handler: F) -> &mut Self
+    where
+        A: ConvexFunctionArgs<Kind = Query> + DeserializeOwned,
+        A::Output: Serialize,
+        F: FnMut(&Database, A) -> anyhow::Result<A::Output> + Send + 'static,
+    {
+        self.register::<A, _>(move |database, args| handler(database, args))
+    }
+
+    /// Implements the mutation identified by `A`. If the handler fails, its writes are undone.
+    pub fn on_mutation<A, F>(&mut self, mut handler: F) -> &mut Self
+    where
+        A: ConvexFunctionArgs<Kind = Mutation> + DeserializeOwned,
+        A::Output: Serialize,
+        F: FnMut(&mut Database, A) -> anyhow::Result<A::Output> + Send + 'static,
+    {
+        self.register::<A, _>(move |database, args| {
+            let snapshot = database.clone();
+            let result = handler(database, args);
+            if result.is_err() {
+                *database = snapshot;
+            }
+            result
+        })
+    }
+
+    /// Implements the action identified by `A`. Like in Convex, actions are not transactional.
+    pub fn on_action<A, F>(&mut self, handler: F) -> &mut Self
+    where
+        A: ConvexFunctionArgs<Kind = Action> + DeserializeOwned,
+        A::Output: Serialize,
+        F: FnMut(&mut Database, A) -> anyhow::Result<A::Output> + Send + 'static,
+    {
+        self.register::<A, _>(handler)
+    }
+
+    fn register<A, F>(&mut self, mut handler: F) -> &mut Self
+    where
+        A: ConvexFunctionArgs + DeserializeOwned,
+        A::Output: Serialize,
+        F: FnMut(&mut Database, A) -> anyhow::Result<A::Output> + Send + 'static,
+    {
+        let handler: Handler = Box::new(move |database, args| match from_value::<A>(ConvexValue::Object(args)) {
+            Ok(args) => match handler(database, args) {
+                Ok(output) => encode_output(&output),
+                Err(e) => FunctionResult::ErrorMessage(e.to_string()),
+            },
+            Err(e) => FunctionResult::ErrorMessage(format!("ArgumentValidationError: {}", e)),
+        });
+
+        self.functions.insert(
+            A::PATH.to_string(),
+            Function {
+                kind: <A::Kind as FunctionKind>::TYPE,
+                handler,
+            },
+        );
+        self
+    }
+
+    fn call(&mut self, kind: FunctionType, path: &str, args: BTreeMap<String, ConvexValue>)
+        -> anyhow::Result<FunctionResult>
+    {
+        let function = self
+            .functions
+            .get_mut(path)
+            .ok_or_else(|| anyhow::anyhow!("No function '{}' is registered with the emulator", path))?;
+        if function.kind != kind {
+            anyhow::bail!("'{}' is a {:?}, not a {:?}", path, function.kind, kind);
+        }
+
+        let result = (function.handler)(&mut self.database, args);
+        if kind != FunctionType::Query {
+            self.notify_subscribers();
+        }
+
+        Ok(result)
+    }
+
+    /// Run subscribed queries again and push results that changed
+    fn notify_subscribers(&mut self)
+    {
+        let mut subscriptions = std::mem::take(&mut self.subscriptions);
+        subscriptions.retain(|subscription| !subscription.sender.is_closed());
+
+        for subscription in &mut subscriptions {
+            let Some(function) = self.functions.get_mut(&subscription.path) else {
+                continue;
+            };
+            let result = (function.handler)(&mut self.database, subscription.args.clone());
+            if result != subscription.last {
+                subscription.last = result.clone();
+                let _ = subscription.sender.unbounded_send(result);
+            }
+        }
+
+        self.subscriptions = subscriptions;
+    }
+}
+
+impl ConvexTransport for Emulator
+{
+    type Subscription = UnboundedReceiver<FunctionResult>;
+
+    async fn subscribe(&mut self, path: &str, args: BTreeMap<String, ConvexValue>) -> anyhow::Result<Self::Subscription>
+    {
+        let result = self.call(FunctionType::Query, path, args.clone())?;
+        let (sender, receiver) = unbounded();
+        sender
+            .unbounded_send(result.clone())
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        self.subscriptions.push(Subscription {
+            path: path.to_string(),
+            args,
+            last: result,
+            sender,
+        });
+
+        Ok(receiver)
+    }
+
+    async fn query(&mut self, path: &str, args: BTreeMap<String, ConvexValue>) -> anyhow::Result<FunctionResult>
+    {
+        self.call(FunctionType::Query, path, args)
+    }
+
+    async fn mutation(&mut self, path: &str, args: BTreeMap<String, ConvexValue>) -> anyhow::Result<FunctionResult>
+    {
+        self.call(FunctionType::Mutation, path, args)
+    }
+
+    async fn action(&mut self, path: &str, args: BTreeMap<String, ConvexValue>) -> anyhow::Result<FunctionResult>
+    {
+        self.call(FunctionType::Action, path, args)
+    }
+}
@@ -1,134 +1,590 @@
 use std::fmt;
 
-/// Errors that can occur during the type generation process.
+pub use convex_typegen_codegen::errors::ConvexTypeGeneratorError;
+
+/// Errors that can occur when decoding a Convex value into a Rust type.
 #[derive(Debug)]
-pub enum ConvexTypeGeneratorError
+pub enum ConvexDecodeError
 {
-    /// The schema file could not be found at the specified path
-    MissingSchemaFile,
-
-    /// Failed to parse a source file
-    ParsingFailed
+    /// The value does not have the shape expected by the target type
+    InvalidValue
     {
-        /// Path to the file that failed to parse
-        file: String,
-        /// Details about the parsing failure
+        /// Path to the offending field, outermost segment first
+        path: Vec<String>,
+        /// Details about the mismatch
         details: String,
     },
 
-    /// The schema file exists but is empty
-    EmptySchemaFile
+    /// The Convex function failed and returned an error message instead of a value
+    FunctionFailed(String),
+
+    /// The Convex function threw a `ConvexError` carrying application data
+    ApplicationError(convex::ConvexError),
+}
+
+impl fmt::Display for ConvexDecodeError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        /// Path to the empty schema file
-        file: String,
-    },
+        match self {
+            Self::InvalidValue { path, details } if path.is_empty() => {
+                write!(f, "Failed to decode value: {}", details)
+            }
+            Self::InvalidValue { path, details } => {
+                write!(f, "Failed to decode value at '{}': {}", path.join("."), details)
+            }
+            Self::FunctionFailed(message) => write!(f, "Convex function failed: {}", message),
+            Self::ApplicationError(error) => write!(f, "Convex function threw an error: {}", error.message),
+        }
+    }
+}
+
+impl std::error::Error for ConvexDecodeError {}
+
+impl serde::de::Error for ConvexDecodeError
+{
+    fn custom<T: fmt::Display>(msg: T) -> Self
+    {
+        Self::InvalidValue {
+            path: Vec::new(),
+            details: msg.to_string(),
+        }
+    }
+}
+
+impl ConvexDecodeError
+{
+    /// Prepends a field name or array index to the path of a decoding error
+    pub(crate) fn with_path_segment(self, segment: impl Into<String>) -> Self
+    {
+        match self {
+            Self::InvalidValue { mut path, details } => {
+                path.insert(0, segment.into());
+                Self::InvalidValue { path, details }
+            }
+            other => other,
+        }
+    }
+}
+
+/// Errors that can occur when calling a Convex function through the typed client methods.
+#[derive(Debug)]
+pub enum ConvexCallError
+{
+    /// The client failed to send the request or receive the response
+    Client(anyhow::Error),
+
+    /// The Convex function failed and returned an error message instead of a value
+    FunctionFailed(String),
+
+    /// The Convex function threw a `ConvexError` carrying application data
+    ApplicationError(convex::ConvexError),
+
+    /// Convex rejected the arguments of the function before running it
+    InvalidArguments(Box<ConvexArgumentError>),
+
+    /// The arguments could not be converted into Convex values
+    Encode(ConvexJsonError),
+
+    /// The function returned a value that does not match its generated return type
+    Decode(ConvexDecodeError),
+}
+
+impl fmt::Display for ConvexCallError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            Self::Client(error) => write!(f, "Convex client error: {}", error),
+            Self::FunctionFailed(message) => write!(f, "Convex function failed: {}", message),
+            Self::ApplicationError(error) => write!(f, "Convex function threw an error: {}", error.message),
+            Self::InvalidArguments(error) => write!(f, "{}", error),
+            Self::Encode(error) => write!(f, "{}", error),
+            Self::Decode(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ConvexCallError
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self {
+            Self::Client(error) => Some(error.as_ref()),
+            Self::InvalidArguments(error) => Some(error.as_ref()),
+            Self::Encode(error) => Some(error),
+            Self::Decode(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<anyhow::Error> for ConvexCallError
+{
+    fn from(error: anyhow::Error) -> Self
+    {
+        Self::Client(error)
+    }
+}
 
-    /// The provided path doesn't have a valid file name component
-    InvalidPath(String),
+impl From<ConvexJsonError> for ConvexCallError
+{
+    fn from(error: ConvexJsonError) -> Self
+    {
+        Self::Encode(error)
+    }
+}
 
-    /// The file name contains invalid Unicode characters
-    InvalidUnicode(String),
-    /// Failed to serialize the AST to JSON
-    SerializationFailed(serde_json::Error),
+impl From<ConvexDecodeError> for ConvexCallError
+{
+    fn from(error: ConvexDecodeError) -> Self
+    {
+        match error {
+            ConvexDecodeError::FunctionFailed(message) => Self::FunctionFailed(message),
+            ConvexDecodeError::ApplicationError(error) => Self::ApplicationError(error),
+            other => Self::Decode(other),
+        }
+    }
+}
+
+/// Arguments rejected by Convex, parsed from the `ArgumentValidationError` message it returns.
+///
+/// See [`crate::validator::parse_argument_error`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexArgumentError
+{
+    /// The function that rejected the arguments, e.g. `users:create`, or empty if unknown
+    pub function_path: String,
+    /// Path to the offending argument, outermost segment first, using the Convex field names
+    pub path: Vec<String>,
+    /// The same path using the generated Rust names, e.g. `CreateUserArgs.address.zip`, when the
+    /// arguments were sent through the typed client methods
+    pub rust_path: Option<String>,
+    /// How the argument was rejected
+    pub kind: ArgumentErrorKind,
+    /// The validator the argument was checked against, e.g. `v.string()`
+    pub expected: String,
+    /// The value Convex received, as printed by Convex
+    pub received: String,
+}
 
-    /// An IO error occurred while reading or writing files
-    IOError
+/// The ways Convex rejects a function argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgumentErrorKind
+{
+    /// The value does not match its validator
+    Mismatch,
+    /// A required field is missing from an object
+    MissingField,
+    /// An object has a field its validator doesn't declare
+    ExtraField,
+}
+
+impl fmt::Display for ConvexArgumentError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        /// Path to the file where the error occurred
-        file: String,
-        /// The underlying IO error
-        error: std::io::Error,
+        if self.function_path.is_empty() {
+            write!(f, "Invalid arguments: ")?;
+        } else {
+            write!(f, "Invalid arguments for '{}': ", self.function_path)?;
+        }
+
+        let location = match &self.rust_path {
+            Some(rust_path) => rust_path.clone(),
+            None if self.path.is_empty() => "arguments".to_string(),
+            None => self.path.join("."),
+        };
+        match self.kind {
+            ArgumentErrorKind::Mismatch => write!(
+                f,
+                "'{}' does not match {}, received {}",
+                location, self.expected, self.received
+            ),
+            ArgumentErrorKind::MissingField => {
+                write!(f, "'{}' is missing, it is required by {}", location, self.expected)
+            }
+            ArgumentErrorKind::ExtraField => write!(f, "'{}' is not declared by {}", location, self.expected),
+        }
+    }
+}
+
+impl std::error::Error for ConvexArgumentError {}
+
+/// Errors returned by [`ConvexClientExt::try_call`](crate::convex::ConvexClientExt::try_call), with
+/// the data thrown by the function decoded into its generated error type.
+#[derive(Debug)]
+pub enum TypedCallError<E>
+{
+    /// The function threw a `ConvexError` whose data matches the generated error type
+    Thrown
+    {
+        /// The message of the error
+        message: String,
+        /// The data the error was thrown with
+        data: E,
     },
 
-    /// The schema file has invalid structure or content
-    InvalidSchema
+    /// The call failed in any other way, including errors whose data does not match
+    Call(ConvexCallError),
+}
+
+impl<E> fmt::Display for TypedCallError<E>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            Self::Thrown { message, .. } => write!(f, "Convex function threw an error: {}", message),
+            Self::Call(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for TypedCallError<E>
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self {
+            Self::Call(error) => Some(error),
+            Self::Thrown { .. } => None,
+        }
+    }
+}
+
+impl<E> From<ConvexCallError> for TypedCallError<E>
+{
+    fn from(error: ConvexCallError) -> Self
+    {
+        Self::Call(error)
+    }
+}
+
+/// Errors that can occur when reading or writing an outbox.
+#[cfg(feature = "outbox")]
+#[derive(Debug)]
+pub enum ConvexOutboxError
+{
+    /// The outbox file could not be read or written
+    Io(std::io::Error),
+
+    /// A record in the outbox file could not be parsed
+    Corrupt
     {
-        /// Context where the invalid schema was found
-        context: String,
-        /// Details about why the schema is invalid
+        /// The line of the invalid record, starting at 1
+        line: usize,
+        /// Details about why the record is invalid
         details: String,
     },
 
-    /// A circular reference was detected in type definitions
-    CircularReference
+    /// An entry could not be serialized
+    Serialization(serde_json::Error),
+}
+
+#[cfg(feature = "outbox")]
+impl fmt::Display for ConvexOutboxError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        /// The path of types that form the circular reference
-        path: Vec<String>,
+        match self {
+            Self::Io(error) => write!(f, "Outbox IO error: {}", error),
+            Self::Corrupt { line, details } => write!(f, "Invalid outbox record on line {}: {}", line, details),
+            Self::Serialization(error) => write!(f, "Failed to serialize outbox entry: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "outbox")]
+impl std::error::Error for ConvexOutboxError {}
+
+#[cfg(feature = "outbox")]
+impl From<std::io::Error> for ConvexOutboxError
+{
+    fn from(error: std::io::Error) -> Self
+    {
+        Self::Io(error)
+    }
+}
+
+#[cfg(feature = "outbox")]
+impl From<serde_json::Error> for ConvexOutboxError
+{
+    fn from(error: serde_json::Error) -> Self
+    {
+        Self::Serialization(error)
+    }
+}
+
+/// Errors returned by the in-memory database of [`crate::emulator`].
+#[derive(Debug)]
+pub enum ConvexEmulatorError
+{
+    /// The table is not defined in the schema
+    UnknownTable(String),
+
+    /// The index is not defined on the table
+    UnknownIndex
+    {
+        /// The table that was read
+        table: String,
+        /// The name of the missing index
+        index: String,
     },
 
-    /// An invalid type name was encountered
-    InvalidType
+    /// No document has the given id
+    DocumentNotFound(String),
+
+    /// A document does not match the validator of its table
+    InvalidDocument
     {
-        /// The invalid type that was found
-        found: String,
-        /// List of valid type names
-        valid_types: Vec<String>,
+        /// The table of the document
+        table: String,
+        /// The first mismatch found
+        error: ConvexValidationError,
+    },
+
+    /// A document breaks Convex's limits, e.g. by setting a system field
+    LimitViolation(Vec<ConvexLimitViolation>),
+
+    /// An index range does not follow the order of the index fields
+    InvalidIndexRange
+    {
+        /// The index that was read
+        index: String,
+        /// Details about what is wrong with the range
+        details: String,
     },
 }
 
-impl fmt::Display for ConvexTypeGeneratorError
+impl fmt::Display for ConvexEmulatorError
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
         match self {
-            Self::MissingSchemaFile => write!(f, "Schema file not found"),
-            Self::ParsingFailed { file, details } => {
-                write!(f, "Failed to parse file '{}': {}", file, details)
-            }
-            Self::EmptySchemaFile { file } => {
-                write!(f, "Schema file '{}' is empty", file)
-            }
-            Self::InvalidPath(path) => {
-                write!(f, "Invalid path: {}", path)
-            }
-            Self::InvalidUnicode(path) => {
-                write!(f, "Path contains invalid Unicode: {}", path)
-            }
-            Self::SerializationFailed(err) => {
-                write!(f, "Failed to serialize AST: {}", err)
-            }
-            Self::IOError { file, error } => {
-                write!(f, "IO error while reading '{}': {}", file, error)
-            }
-            Self::InvalidSchema { context, details } => {
-                write!(f, "Invalid schema at {}: {}", context, details)
-            }
-            Self::CircularReference { path } => {
-                write!(f, "Circular type reference detected: {}", path.join(" -> "))
-            }
-            Self::InvalidType { found, valid_types } => {
-                write!(f, "Invalid type '{}'. Valid types are: {}", found, valid_types.join(", "))
+            Self::UnknownTable(table) => write!(f, "Table '{}' is not defined in the schema", table),
+            Self::UnknownIndex { table, index } => write!(f, "Index '{}' is not defined on table '{}'", index, table),
+            Self::DocumentNotFound(id) => write!(f, "No document with id '{}'", id),
+            Self::InvalidDocument { table, error } => write!(f, "Invalid document for table '{}': {}", table, error),
+            Self::LimitViolation(violations) => {
+                let violations = violations.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(f, "Document breaks Convex limits: {}", violations.join("; "))
             }
+            Self::InvalidIndexRange { index, details } => write!(f, "Invalid range for index '{}': {}", index, details),
         }
     }
 }
 
-// Add this implementation to convert std::io::Error to ConvexTypeGeneratorError
-impl From<std::io::Error> for ConvexTypeGeneratorError
+impl std::error::Error for ConvexEmulatorError
 {
-    fn from(error: std::io::Error) -> Self
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self {
+            Self::InvalidDocument { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Errors that can occur when reading a value in Convex's JSON format.
+#[derive(Debug)]
+pub struct ConvexJsonError
+{
+    /// Path to the offending value, outermost segment first
+    pub path: Vec<String>,
+    /// Details about why the value is invalid
+    pub details: String,
+}
+
+impl fmt::Display for ConvexJsonError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        ConvexTypeGeneratorError::IOError {
-            file: String::new(),
-            error,
+        if self.path.is_empty() {
+            write!(f, "Invalid Convex JSON: {}", self.details)
+        } else {
+            write!(f, "Invalid Convex JSON at '{}': {}", self.path.join("."), self.details)
         }
     }
 }
 
-// Implement std::error::Error for better error handling
-impl std::error::Error for ConvexTypeGeneratorError {}
+impl std::error::Error for ConvexJsonError {}
 
-impl ConvexTypeGeneratorError
+/// Lets arguments converted with `From` stand in where a fallible conversion is expected
+impl From<std::convert::Infallible> for ConvexJsonError
 {
-    /// Adds file context to an IO error
-    pub fn with_file_context(self, file: impl Into<String>) -> Self
+    fn from(never: std::convert::Infallible) -> Self
+    {
+        match never {}
+    }
+}
+
+/// Errors that can occur when converting JSON into Convex values against a validator.
+#[derive(Debug)]
+pub struct ConvexCoercionError
+{
+    /// Path to the offending value, outermost segment first
+    pub path: Vec<String>,
+    /// The validator the value was expected to match, e.g. `v.int64()`
+    pub expected: String,
+    /// What was found instead
+    pub found: String,
+}
+
+impl fmt::Display for ConvexCoercionError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        if self.path.is_empty() {
+            write!(f, "Expected {}, found {}", self.expected, self.found)
+        } else {
+            write!(
+                f,
+                "Expected {} at '{}', found {}",
+                self.expected,
+                self.path.join("."),
+                self.found
+            )
+        }
+    }
+}
+
+impl std::error::Error for ConvexCoercionError {}
+
+/// A value that does not match a Convex validator.
+#[derive(Debug)]
+pub struct ConvexValidationError
+{
+    /// Path to the offending value, outermost segment first
+    pub path: Vec<String>,
+    /// The validator the value was expected to match, e.g. `v.id("users")`
+    pub expected: String,
+    /// What was found instead
+    pub found: String,
+}
+
+impl fmt::Display for ConvexValidationError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        if self.path.is_empty() {
+            write!(f, "Validation failed: expected {}, found {}", self.expected, self.found)
+        } else {
+            write!(
+                f,
+                "Validation failed at '{}': expected {}, found {}",
+                self.path.join("."),
+                self.expected,
+                self.found
+            )
+        }
+    }
+}
+
+impl std::error::Error for ConvexValidationError {}
+
+/// A Convex value limit broken by a value, found before it is sent to the backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexLimitViolation
+{
+    /// Path to the offending value, outermost segment first
+    pub path: Vec<String>,
+    /// Which limit was broken
+    pub kind: LimitViolationKind,
+}
+
+/// The limits checked by [`crate::limits`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum LimitViolationKind
+{
+    /// The value is larger than the maximum value size
+    ValueTooLarge
+    {
+        /// Approximate size of the value in bytes
+        size: usize,
+        /// Maximum size in bytes
+        limit: usize,
+    },
+    /// An array has more elements than allowed
+    ArrayTooLong
+    {
+        /// Number of elements in the array
+        length: usize,
+        /// Maximum number of elements
+        limit: usize,
+    },
+    /// An object has more fields than allowed
+    TooManyFields
+    {
+        /// Number of fields in the object
+        count: usize,
+        /// Maximum number of fields
+        limit: usize,
+    },
+    /// Arrays and objects are nested more deeply than allowed
+    NestingTooDeep
+    {
+        /// Depth at which the limit was exceeded
+        depth: usize,
+        /// Maximum nesting depth
+        limit: usize,
+    },
+    /// A field name is the empty string
+    EmptyFieldName,
+    /// A field name starts with `$`, or a top-level document field starts with `_`
+    ReservedFieldName(String),
+    /// A field name contains non-ASCII or control characters
+    InvalidFieldName(String),
+    /// A field name is longer than allowed
+    FieldNameTooLong
+    {
+        /// Length of the field name in bytes
+        length: usize,
+        /// Maximum length in bytes
+        limit: usize,
+    },
+    /// The value could not be converted to a Convex value at all
+    Unserializable(String),
+}
+
+impl fmt::Display for LimitViolationKind
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
         match self {
-            Self::IOError { error, .. } => Self::IOError {
-                file: file.into(),
-                error,
-            },
-            other => other,
+            LimitViolationKind::ValueTooLarge { size, limit } => {
+                write!(f, "value is {} bytes, the limit is {} bytes", size, limit)
+            }
+            LimitViolationKind::ArrayTooLong { length, limit } => {
+                write!(f, "array has {} elements, the limit is {}", length, limit)
+            }
+            LimitViolationKind::TooManyFields { count, limit } => {
+                write!(f, "object has {} fields, the limit is {}", count, limit)
+            }
+            LimitViolationKind::NestingTooDeep { depth, limit } => {
+                write!(f, "value is nested {} levels deep, the limit is {}", depth, limit)
+            }
+            LimitViolationKind::EmptyFieldName => write!(f, "field name is empty"),
+            LimitViolationKind::ReservedFieldName(name) => write!(f, "field name '{}' is reserved", name),
+            LimitViolationKind::InvalidFieldName(name) => {
+                write!(f, "field name '{}' must only contain printable ASCII characters", name)
+            }
+            LimitViolationKind::FieldNameTooLong { length, limit } => {
+                write!(f, "field name is {} bytes long, the limit is {}", length, limit)
+            }
+            LimitViolationKind::Unserializable(details) => write!(f, "value cannot be serialized: {}", details),
+        }
+    }
+}
+
+impl fmt::Display for ConvexLimitViolation
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        if self.path.is_empty() {
+            write!(f, "Limit exceeded: {}", self.kind)
+        } else {
+            write!(f, "Limit exceeded at '{}': {}", self.path.join("."), self.kind)
         }
     }
 }
+
+impl std::error::Error for ConvexLimitViolation {}
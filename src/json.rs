@@ -0,0 +1,404 @@
+use std::collections::BTreeMap;
+use std::num::FpCategory;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use convex::Value as ConvexValue;
+use serde::Serialize;
+use serde_json::{json, Map, Value as JsonValue};
+
+use crate::errors::ConvexJsonError;
+
+/// Converts a Convex value into Convex's JSON format.
+///
+/// This is the encoding used by the Convex HTTP API and snapshot exports: `Int64` values become
+/// `{"$integer": "<base64>"}`, bytes become `{"$bytes": "<base64>"}` and floats that JSON cannot
+/// represent (`NaN`, infinities and `-0`) become `{"$float": "<base64>"}`.
+pub fn to_convex_json(value: ConvexValue) -> JsonValue
+{
+    match value {
+        ConvexValue::Null => JsonValue::Null,
+        ConvexValue::Int64(i) => json!({ "$integer": encode_int64(i) }),
+        ConvexValue::Float64(f) if is_special_float(f) => json!({ "$float": encode_float64(f) }),
+        ConvexValue::Float64(f) => json!(f),
+        ConvexValue::Boolean(b) => JsonValue::Bool(b),
+        ConvexValue::String(s) => JsonValue::String(s),
+        ConvexValue::Bytes(b) => json!({ "$bytes": STANDARD.encode(b) }),
+        ConvexValue::Array(arr) => JsonValue::Array(arr.into_iter().map(to_convex_json).collect()),
+        ConvexValue::Object(map) => JsonValue::Object(map.into_iter().map(|(k, v)| (k, to_convex_json(v))).collect()),
+    }
+}
+
+/// Converts a value in Convex's JSON format into a Convex value.
+///
+/// Plain JSON numbers always become `Float64`, matching how Convex reads them. Integers must be
+/// wrapped in `{"$integer": ...}` to be read as `Int64`.
+///
+/// # Errors
+/// Returns an error if a `$`-wrapper holds an invalid payload, an object uses an unknown
+/// `$`-prefixed key, or a number cannot be represented as an `f64`.
+pub fn from_convex_json(json: JsonValue) -> Result<ConvexValue, ConvexJsonError>
+{
+    let mut path = Vec::new();
+    json_to_value(json, false, &mut path)
+}
+
+/// Converts serialized function arguments into Convex values.
+///
+/// Reads Convex's JSON format like [`from_convex_json`], except that plain integers become `Int64`,
+/// as they do with [`IntoConvexValue`](crate::convex::IntoConvexValue).
+pub(crate) fn from_args_json(json: JsonValue) -> Result<ConvexValue, ConvexJsonError>
+{
+    let mut path = Vec::new();
+    json_to_value(json, true, &mut path)
+}
+
+fn json_to_value(json: JsonValue, plain_integers: bool, path: &mut Vec<String>) -> Result<ConvexValue, ConvexJsonError>
+{
+    match json {
+        JsonValue::Null => Ok(ConvexValue::Null),
+        JsonValue::Bool(b) => Ok(ConvexValue::Boolean(b)),
+        JsonValue::Number(n) => {
+            let value = match n.as_i64() {
+                Some(i) if plain_integers => Some(ConvexValue::Int64(i)),
+                _ => n.as_f64().map(ConvexValue::Float64),
+            };
+            value.ok_or_else(|| ConvexJsonError {
+                path: path.clone(),
+                details: format!("number {} cannot be represented as a float64", n),
+            })
+        }
+        JsonValue::String(s) => Ok(ConvexValue::String(s)),
+        JsonValue::Array(arr) => {
+            let mut values = Vec::with_capacity(arr.len());
+            for (i, item) in arr.into_iter().enumerate() {
+                path.push(i.to_string());
+                values.push(json_to_value(item, plain_integers, path)?);
+                path.pop();
+            }
+            Ok(ConvexValue::Array(values))
+        }
+        JsonValue::Object(map) => {
+            if let Some(value) = decode_wrapper(&map) {
+                return value.map_err(|details| ConvexJsonError {
+                    path: path.clone(),
+                    details,
+                });
+            }
+
+            let mut fields = BTreeMap::new();
+            for (key, value) in map {
+                if key.starts_with('$') {
+                    return Err(ConvexJsonError {
+                        path: path.clone(),
+                        details: format!("unknown reserved key '{}'", key),
+                    });
+                }
+                path.push(key.clone());
+                fields.insert(key, json_to_value(value, plain_integers, path)?);
+                path.pop();
+            }
+            Ok(ConvexValue::Object(fields))
+        }
+    }
+}
+
+/// Serializes generated function arguments into the map of JSON values sent to Convex.
+///
+/// # Errors
+/// Returns an error if the arguments fail to serialize or don't serialize to a JSON object.
+pub fn to_args_map<T: Serialize>(args: T) -> Result<BTreeMap<String, JsonValue>, ConvexJsonError>
+{
+    let invalid = |details: String| ConvexJsonError {
+        path: Vec::new(),
+        details,
+    };
+    match serde_json::to_value(args).map_err(|e| invalid(e.to_string()))? {
+        JsonValue::Object(map) => Ok(map.into_iter().collect()),
+        other => Err(invalid(format!("arguments must serialize to an object, found {}", other))),
+    }
+}
+
+/// Decodes a single-key `$integer`, `$float` or `$bytes` object.
+///
+/// Returns `None` if the object is not one of these wrappers.
+pub(crate) fn decode_wrapper(map: &Map<String, JsonValue>) -> Option<Result<ConvexValue, String>>
+{
+    if map.len() != 1 {
+        return None;
+    }
+
+    let (key, value) = map.iter().next()?;
+    let decode: fn(&str) -> Result<ConvexValue, String> = match key.as_str() {
+        "$integer" => |s| decode_int64(s).map(ConvexValue::Int64),
+        "$float" => |s| decode_float64(s).map(ConvexValue::Float64),
+        "$bytes" => |s| decode_bytes(s).map(ConvexValue::Bytes),
+        _ => return None,
+    };
+
+    Some(match value.as_str() {
+        Some(encoded) => decode(encoded),
+        None => Err(format!("'{}' must hold a base64 string", key)),
+    })
+}
+
+fn is_special_float(f: f64) -> bool
+{
+    let negative_zero = f == 0.0 && f.is_sign_negative();
+    negative_zero || matches!(f.classify(), FpCategory::Infinite | FpCategory::Nan)
+}
+
+fn encode_int64(i: i64) -> String
+{
+    STANDARD.encode(i.to_le_bytes())
+}
+
+fn decode_int64(encoded: &str) -> Result<i64, String>
+{
+    let bytes = decode_bytes(encoded)?;
+    let bytes: [u8; 8] = bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| format!("'$integer' must hold 8 bytes, found {}", b.len()))?;
+    Ok(i64::from_le_bytes(bytes))
+}
+
+fn encode_float64(f: f64) -> String
+{
+    STANDARD.encode(f.to_le_bytes())
+}
+
+fn decode_float64(encoded: &str) -> Result<f64, String>
+{
+    let bytes = decode_bytes(encoded)?;
+    let bytes: [u8; 8] = bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| format!("'$float' must hold 8 bytes, found {}", b.len()))?;
+    Ok(f64::from_le_bytes(bytes))
+}
+
+fn decode_bytes(encoded: &str) -> Result<Vec<u8>, String>
+{
+    STANDARD.decode(encoded).map_err(|e| format!("invalid base64: {}", e))
+}
+
+/// Serde helpers for `i64` fields, for use with `#[serde(with = "convex_typegen::json::int64")]`.
+///
+/// Values serialize as `{"$integer": "<base64>"}`. Deserialization accepts that form as well as
+/// plain integers, so the same field can be read from Convex JSON and from a `convex::Value`.
+pub mod int64
+{
+    use std::fmt;
+
+    use serde::de::{self, MapAccess, Visitor};
+    use serde::ser::SerializeMap;
+    use serde::{Deserializer, Serializer};
+
+    /// Serializes an `i64` as `{"$integer": "<base64>"}`.
+    pub fn serialize<S: Serializer>(value: &i64, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("$integer", &super::encode_int64(*value))?;
+        map.end()
+    }
+
+    /// Deserializes an `i64` from `{"$integer": "<base64>"}` or a plain integer.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error>
+    {
+        deserializer.deserialize_any(Int64Visitor)
+    }
+
+    struct Int64Visitor;
+
+    impl<'de> Visitor<'de> for Int64Visitor
+    {
+        type Value = i64;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            f.write_str("an int64 or {\"$integer\": \"<base64>\"}")
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<i64, E>
+        {
+            Ok(v)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<i64, E>
+        {
+            i64::try_from(v).map_err(|_| E::custom(format!("{} is out of range for an int64", v)))
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<i64, E>
+        {
+            if v.fract() == 0.0 && v >= i64::MIN as f64 && v < i64::MAX as f64 {
+                Ok(v as i64)
+            } else {
+                Err(E::custom(format!("{} is not an integer", v)))
+            }
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<i64, A::Error>
+        {
+            let encoded = match map.next_key::<String>()? {
+                Some(key) if key == "$integer" => map.next_value::<String>()?,
+                Some(key) => return Err(de::Error::unknown_field(&key, &["$integer"])),
+                None => return Err(de::Error::invalid_length(0, &self)),
+            };
+            if map.next_key::<de::IgnoredAny>()?.is_some() {
+                return Err(de::Error::invalid_length(2, &self));
+            }
+            super::decode_int64(&encoded).map_err(de::Error::custom)
+        }
+    }
+
+    /// Serde helpers for `Option<i64>` fields.
+    ///
+    /// Use together with `#[serde(default)]` so a missing field reads as `None`.
+    pub mod option
+    {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        struct Wrapper(i64);
+
+        impl Serialize for Wrapper
+        {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            {
+                super::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Wrapper
+        {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+            {
+                super::deserialize(deserializer).map(Wrapper)
+            }
+        }
+
+        /// Serializes an `Option<i64>`, writing `None` as null.
+        pub fn serialize<S: Serializer>(value: &Option<i64>, serializer: S) -> Result<S::Ok, S::Error>
+        {
+            value.map(Wrapper).serialize(serializer)
+        }
+
+        /// Deserializes an `Option<i64>`, reading null as `None`.
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error>
+        {
+            Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|w| w.0))
+        }
+    }
+}
+
+/// Serde helpers for `Vec<u8>` fields, for use with `#[serde(with = "convex_typegen::json::bytes")]`.
+///
+/// Values serialize as `{"$bytes": "<base64>"}`. Deserialization accepts that form as well as raw
+/// bytes and sequences of integers.
+pub mod bytes
+{
+    use std::fmt;
+
+    use serde::de::{self, MapAccess, SeqAccess, Visitor};
+    use serde::ser::SerializeMap;
+    use serde::{Deserializer, Serializer};
+
+    /// Serializes bytes as `{"$bytes": "<base64>"}`.
+    pub fn serialize<S: Serializer>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    {
+        use base64::Engine;
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("$bytes", &super::STANDARD.encode(value))?;
+        map.end()
+    }
+
+    /// Deserializes bytes from `{"$bytes": "<base64>"}`, raw bytes or a sequence of integers.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    {
+        deserializer.deserialize_any(BytesVisitor)
+    }
+
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor
+    {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            f.write_str("bytes or {\"$bytes\": \"<base64>\"}")
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E>
+        {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E>
+        {
+            Ok(v)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error>
+        {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            Ok(bytes)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Vec<u8>, A::Error>
+        {
+            let encoded = match map.next_key::<String>()? {
+                Some(key) if key == "$bytes" => map.next_value::<String>()?,
+                Some(key) => return Err(de::Error::unknown_field(&key, &["$bytes"])),
+                None => return Err(de::Error::invalid_length(0, &self)),
+            };
+            if map.next_key::<de::IgnoredAny>()?.is_some() {
+                return Err(de::Error::invalid_length(2, &self));
+            }
+            super::decode_bytes(&encoded).map_err(de::Error::custom)
+        }
+    }
+
+    /// Serde helpers for `Option<Vec<u8>>` fields.
+    ///
+    /// Use together with `#[serde(default)]` so a missing field reads as `None`.
+    pub mod option
+    {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        struct Borrowed<'a>(&'a [u8]);
+
+        impl Serialize for Borrowed<'_>
+        {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            {
+                super::serialize(self.0, serializer)
+            }
+        }
+
+        struct Owned(Vec<u8>);
+
+        impl<'de> Deserialize<'de> for Owned
+        {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+            {
+                super::deserialize(deserializer).map(Owned)
+            }
+        }
+
+        /// Serializes an `Option<Vec<u8>>`, writing `None` as null.
+        pub fn serialize<S: Serializer>(value: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error>
+        {
+            value.as_deref().map(Borrowed).serialize(serializer)
+        }
+
+        /// Deserializes an `Option<Vec<u8>>`, reading null as `None`.
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error>
+        {
+            Ok(Option::<Owned>::deserialize(deserializer)?.map(|o| o.0))
+        }
+    }
+}
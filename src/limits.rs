@@ -0,0 +1,198 @@
+use convex::Value as ConvexValue;
+use serde::Serialize;
+
+use crate::errors::{ConvexLimitViolation, LimitViolationKind};
+use crate::json::from_convex_json;
+
+/// The maximum size of a document or value, in bytes.
+pub const MAX_VALUE_SIZE: usize = 1 << 20;
+
+/// The maximum number of elements in an array.
+pub const MAX_ARRAY_LENGTH: usize = 8192;
+
+/// The maximum number of fields in an object.
+pub const MAX_OBJECT_FIELDS: usize = 1024;
+
+/// The maximum nesting depth of arrays and objects.
+pub const MAX_NESTING_DEPTH: usize = 16;
+
+/// The maximum length of a field name, in bytes.
+pub const MAX_FIELD_NAME_LENGTH: usize = 1024;
+
+/// Checks a document against Convex's limits before it is inserted.
+///
+/// On top of the checks made by [`check_value`], the top-level fields of a document may not start
+/// with `_`, since those names are reserved for system fields like `_id`.
+///
+/// # Errors
+/// Returns every violation found, each with the path to the offending value.
+pub fn check_document(value: &ConvexValue) -> Result<(), Vec<ConvexLimitViolation>>
+{
+    let mut violations = Vec::new();
+
+    if let ConvexValue::Object(fields) = value {
+        for name in fields.keys().filter(|name| name.starts_with('_')) {
+            violations.push(ConvexLimitViolation {
+                path: vec![name.clone()],
+                kind: LimitViolationKind::ReservedFieldName(name.clone()),
+            });
+        }
+    }
+
+    check(value, &mut Vec::new(), &mut violations);
+    check_size(value, &mut violations);
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Checks a value, such as function arguments, against Convex's limits.
+///
+/// # Errors
+/// Returns every violation found, each with the path to the offending value.
+pub fn check_value(value: &ConvexValue) -> Result<(), Vec<ConvexLimitViolation>>
+{
+    let mut violations = Vec::new();
+    check(value, &mut Vec::new(), &mut violations);
+    check_size(value, &mut violations);
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Serializes a value to Convex JSON and checks the result as a document.
+///
+/// This works for any type generated by this crate, as well as other serializable types.
+///
+/// # Errors
+/// Returns every violation found. A value that cannot be serialized is reported as a single
+/// [`LimitViolationKind::Unserializable`] violation.
+pub fn check_serialized_document<T: Serialize>(value: &T) -> Result<(), Vec<ConvexLimitViolation>>
+{
+    let value = serde_json::to_value(value)
+        .map_err(|e| e.to_string())
+        .and_then(|json| from_convex_json(json).map_err(|e| e.to_string()))
+        .map_err(|details| {
+            vec![ConvexLimitViolation {
+                path: Vec::new(),
+                kind: LimitViolationKind::Unserializable(details),
+            }]
+        })?;
+
+    check_document(&value)
+}
+
+/// Approximates the number of bytes Convex counts towards a value's size limit.
+pub fn value_size(value: &ConvexValue) -> usize
+{
+    match value {
+        ConvexValue::Null | ConvexValue::Boolean(_) => 1,
+        ConvexValue::Int64(_) | ConvexValue::Float64(_) => 9,
+        ConvexValue::String(s) => s.len() + 2,
+        ConvexValue::Bytes(b) => b.len() + 2,
+        ConvexValue::Array(items) => 2 + items.iter().map(value_size).sum::<usize>(),
+        ConvexValue::Object(fields) => {
+            2 + fields
+                .iter()
+                .map(|(name, value)| name.len() + 1 + value_size(value))
+                .sum::<usize>()
+        }
+    }
+}
+
+fn check_size(value: &ConvexValue, violations: &mut Vec<ConvexLimitViolation>)
+{
+    let size = value_size(value);
+    if size > MAX_VALUE_SIZE {
+        violations.push(ConvexLimitViolation {
+            path: Vec::new(),
+            kind: LimitViolationKind::ValueTooLarge {
+                size,
+                limit: MAX_VALUE_SIZE,
+            },
+        });
+    }
+}
+
+fn check(value: &ConvexValue, path: &mut Vec<String>, violations: &mut Vec<ConvexLimitViolation>)
+{
+    let depth = path.len() + 1;
+    let nested = matches!(value, ConvexValue::Array(_) | ConvexValue::Object(_));
+
+    if nested && depth > MAX_NESTING_DEPTH {
+        violations.push(ConvexLimitViolation {
+            path: path.clone(),
+            kind: LimitViolationKind::NestingTooDeep {
+                depth,
+                limit: MAX_NESTING_DEPTH,
+            },
+        });
+        return;
+    }
+
+    match value {
+        ConvexValue::Array(items) => {
+            if items.len() > MAX_ARRAY_LENGTH {
+                violations.push(ConvexLimitViolation {
+                    path: path.clone(),
+                    kind: LimitViolationKind::ArrayTooLong {
+                        length: items.len(),
+                        limit: MAX_ARRAY_LENGTH,
+                    },
+                });
+            }
+            for (i, item) in items.iter().enumerate() {
+                path.push(i.to_string());
+                check(item, path, violations);
+                path.pop();
+            }
+        }
+        ConvexValue::Object(fields) => {
+            if fields.len() > MAX_OBJECT_FIELDS {
+                violations.push(ConvexLimitViolation {
+                    path: path.clone(),
+                    kind: LimitViolationKind::TooManyFields {
+                        count: fields.len(),
+                        limit: MAX_OBJECT_FIELDS,
+                    },
+                });
+            }
+            for (name, field) in fields {
+                path.push(name.clone());
+                if let Some(kind) = check_field_name(name) {
+                    violations.push(ConvexLimitViolation {
+                        path: path.clone(),
+                        kind,
+                    });
+                }
+                check(field, path, violations);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn check_field_name(name: &str) -> Option<LimitViolationKind>
+{
+    if name.is_empty() {
+        Some(LimitViolationKind::EmptyFieldName)
+    } else if name.starts_with('$') {
+        Some(LimitViolationKind::ReservedFieldName(name.to_string()))
+    } else if !name.chars().all(|c| c.is_ascii() && !c.is_ascii_control()) {
+        Some(LimitViolationKind::InvalidFieldName(name.to_string()))
+    } else if name.len() > MAX_FIELD_NAME_LENGTH {
+        Some(LimitViolationKind::FieldNameTooLong {
+            length: name.len(),
+            limit: MAX_FIELD_NAME_LENGTH,
+        })
+    } else {
+        None
+    }
+}
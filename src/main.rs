@@ -0,0 +1,175 @@
+use std::ops::ControlFlow;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use convex_typegen::convex::{ConvexFunctions, ConvexSchema};
+use convex_typegen::errors::ConvexTypeGeneratorError;
+use convex_typegen::watch::{watch, WatchEvent};
+use convex_typegen::{check, generate, parse, Configuration};
+use serde::Serialize;
+
+/// Generate Rust types from a Convex schema and functions.
+#[derive(Parser)]
+#[command(name = "convex-typegen", version)]
+struct Cli
+{
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command
+{
+    /// Generate the Rust types and write them to the output file
+    Generate
+    {
+        #[command(flatten)]
+        sources: Sources,
+
+        /// Output file path for the generated Rust types
+        #[arg(long, short, default_value = "src/convex_types.rs")]
+        out_file: String,
+    },
+
+    /// Fail with a diff if the output file is out of date, without writing it
+    Check
+    {
+        #[command(flatten)]
+        sources: Sources,
+
+        /// Output file path for the generated Rust types
+        #[arg(long, short, default_value = "src/convex_types.rs")]
+        out_file: String,
+    },
+
+    /// Generate the Rust types, then regenerate them whenever the sources change
+    Watch
+    {
+        #[command(flatten)]
+        sources: Sources,
+
+        /// Output file path for the generated Rust types
+        #[arg(long, short, default_value = "src/convex_types.rs")]
+        out_file: String,
+    },
+
+    /// Parse the schema and function files without generating anything
+    Validate
+    {
+        #[command(flatten)]
+        sources: Sources,
+    },
+
+    /// Print the parsed schema and functions as JSON
+    Print
+    {
+        #[command(flatten)]
+        sources: Sources,
+
+        /// Print only the schema or only the functions
+        #[arg(value_enum)]
+        only: Option<PrintTarget>,
+    },
+}
+
+/// The Convex files to read, as in [`Configuration`].
+#[derive(Args)]
+struct Sources
+{
+    /// Path to the Convex schema file
+    #[arg(long, short, default_value = "convex/schema.ts")]
+    schema_path: PathBuf,
+
+    /// Path to a Convex function file, can be repeated
+    #[arg(long = "function", short, value_name = "PATH")]
+    function_paths: Vec<PathBuf>,
+}
+
+impl Sources
+{
+    fn into_config(self) -> Configuration
+    {
+        Configuration::default()
+            .with_schema_path(self.schema_path)
+            .with_function_paths(self.function_paths)
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PrintTarget
+{
+    Schema,
+    Functions,
+}
+
+/// The output of `print` without a target.
+#[derive(Serialize)]
+struct Parsed<'a>
+{
+    schema: &'a ConvexSchema,
+    functions: &'a ConvexFunctions,
+}
+
+fn main() -> ExitCode
+{
+    // Usage errors exit with code 2 from clap, failures below with code 1
+    let cli = Cli::parse();
+
+    match run(cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), ConvexTypeGeneratorError>
+{
+    match command {
+        Command::Generate { sources, out_file } => {
+            let config = sources.into_config().with_out_file(out_file);
+            let out_file = config.output_path()?;
+            generate(config)?;
+            eprintln!("Generated {}", out_file.display());
+        }
+        Command::Check { sources, out_file } => {
+            let config = sources.into_config().with_out_file(out_file);
+            let out_file = config.output_path()?;
+            check(config)?;
+            eprintln!("{} is up to date", out_file.display());
+        }
+        Command::Watch { sources, out_file } => {
+            let config = sources.into_config().with_out_file(out_file);
+            watch(config, |event| {
+                match event {
+                    WatchEvent::Generated { out_file, elapsed } => {
+                        eprintln!("Generated {} in {:.0?}", out_file.display(), elapsed)
+                    }
+                    WatchEvent::Unchanged { out_file } => eprintln!("{} is up to date", out_file.display()),
+                    WatchEvent::Failed(error) => eprintln!("error: {}", error),
+                }
+                ControlFlow::Continue(())
+            })?;
+        }
+        Command::Validate { sources } => {
+            let (schema, functions) = parse(&sources.into_config())?;
+            eprintln!("Parsed {} tables and {} functions", schema.tables.len(), functions.len());
+        }
+        Command::Print { sources, only } => {
+            let (schema, functions) = parse(&sources.into_config())?;
+            let output = match only {
+                Some(PrintTarget::Schema) => serde_json::to_string_pretty(&schema),
+                Some(PrintTarget::Functions) => serde_json::to_string_pretty(&functions),
+                None => serde_json::to_string_pretty(&Parsed {
+                    schema: &schema,
+                    functions: &functions,
+                }),
+            };
+            println!("{}", output.map_err(ConvexTypeGeneratorError::SerializationFailed)?);
+        }
+    }
+
+    Ok(())
+}
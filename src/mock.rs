@@ -0,0 +1,244 @@
+use std::collections::{BTreeMap, HashMap};
+
+use convex::{FunctionResult, Value as ConvexValue};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::client::{ConvexFunctionArgs, ConvexTransport};
+use crate::decode::from_value;
+use crate::json::from_convex_json;
+use crate::registry::{FunctionInfo, FunctionType, Visibility};
+
+/// A call received by a [`MockTransport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedCall
+{
+    /// The path of the function, e.g. `"messages:send"`
+    pub path: String,
+    /// Whether the function was called as a query, a mutation or an action
+    pub function_type: FunctionType,
+    /// The arguments the function was called with
+    pub args: BTreeMap<String, ConvexValue>,
+}
+
+type Handler = Box<dyn FnMut(BTreeMap<String, ConvexValue>) -> anyhow::Result<FunctionResult> + Send>;
+
+/// An in-process stand-in for a Convex deployment.
+///
+/// Register a typed handler or a canned response for each function a test calls, run the code
+/// under test against the mock through [`ConvexClientExt`](crate::convex::ConvexClientExt), then
+/// assert on the recorded calls. Calls to functions without a response fail like a lost
+/// connection would.
+///
+/// ```
+/// use convex_typegen::client::{ConvexFunctionArgs, Query};
+/// use convex_typegen::convex::ConvexClientExt;
+/// use convex_typegen::mock::MockTransport;
+/// # #[derive(serde::Serialize, serde::Deserialize)]
+/// # struct CountArgs { room: String }
+/// # impl ConvexFunctionArgs for CountArgs {
+/// #     const PATH: &'static str = "messages:count";
+/// #     type Kind = Query;
+/// #     type Output = f64;
+/// # }
+///
+/// let mut mock = MockTransport::new();
+/// mock.on(|args: CountArgs| if args.room == "lobby" { 3.0 } else { 0.0 });
+///
+/// let count = futures::executor::block_on(mock.call_query(CountArgs {
+///     room: "lobby".to_string(),
+/// }));
+/// assert_eq!(count.unwrap(), 3.0);
+/// assert_eq!(mock.calls_to::<CountArgs>().len(), 1);
+/// ```
+#[derive(Default)]
+pub struct MockTransport
+{
+    functions: Option<&'static [FunctionInfo]>,
+    handlers: HashMap<String, Handler>,
+    calls: Vec<RecordedCall>,
+}
+
+impl MockTransport
+{
+    /// Creates a mock that accepts calls to any function.
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    /// Creates a mock that only accepts calls to public functions in a generated `FUNCTIONS`
+    /// registry, made with the right kind.
+    pub fn for_functions(functions: &'static [FunctionInfo]) -> Self
+    {
+        Self {
+            functions: Some(functions),
+            ..Self::default()
+        }
+    }
+
+    /// Answers calls to the function identified by `A` with a typed handler.
+    ///
+    /// Arguments that do not decode into `A` are answered with an error message, as Convex's own
+    /// argument validation would.
+    pub fn on<A, F>(&mut self, mut handler: F) -> &mut Self
+    where
+        A: ConvexFunctionArgs + DeserializeOwned,
+        A::Output: Serialize,
+        F: FnMut(A) -> A::Output + Send + 'static,
+    {
+        self.handlers.insert(
+            A::PATH.to_string(),
+            Box::new(move |args| match from_value::<A>(ConvexValue::Object(args)) {
+                Ok(args) => Ok(encode_output(&handler(args))),
+                Err(e) => Ok(invalid_arguments(e)),
+            }),
+        );
+        self
+    }
+
+    /// Answers calls to the function identified by `A` with a raw result computed from the
+    /// arguments, e.g. a `FunctionResult::ErrorMessage` for some of them. Returning an error
+    /// simulates losing the connection to Convex.
+    pub fn on_result<A, F>(&mut self, mut handler: F) -> &mut Self
+    where
+        A: ConvexFunctionArgs + DeserializeOwned,
+        F: FnMut(A) -> anyhow::Result<FunctionResult> + Send + 'static,
+    {
+        self.handlers.insert(
+            A::PATH.to_string(),
+            Box::new(move |args| match from_value::<A>(ConvexValue::Object(args)) {
+                Ok(args) => handler(args),
+                Err(e) => Ok(invalid_arguments(e)),
+            }),
+        );
+        self
+    }
+
+    /// Answers every call to the function identified by `A` with the same value.
+    pub fn respond<A>(&mut self, output: A::Output) -> &mut Self
+    where
+        A: ConvexFunctionArgs,
+        A::Output: Serialize,
+    {
+        let result = encode_output(&output);
+        self.respond_with::<A>(result)
+    }
+
+    /// Answers every call to the function identified by `A` with the same raw result, e.g. a
+    /// `FunctionResult::ErrorMessage` to simulate a failing function.
+    pub fn respond_with<A: ConvexFunctionArgs>(&mut self, result: FunctionResult) -> &mut Self
+    {
+        self.handlers
+            .insert(A::PATH.to_string(), Box::new(move |_| Ok(result.clone())));
+        self
+    }
+
+    /// Every call received so far, oldest first.
+    pub fn calls(&self) -> &[RecordedCall]
+    {
+        &self.calls
+    }
+
+    /// The arguments of every call to the function identified by `A`, decoded into `A`.
+    ///
+    /// # Panics
+    /// Panics if recorded arguments do not decode into `A`.
+    pub fn calls_to<A>(&self) -> Vec<A>
+    where
+        A: ConvexFunctionArgs + DeserializeOwned,
+    {
+        self.calls
+            .iter()
+            .filter(|call| call.path == A::PATH)
+            .map(|call| {
+                from_value(ConvexValue::Object(call.args.clone()))
+                    .unwrap_or_else(|e| panic!("Recorded call to '{}' has invalid arguments: {}", A::PATH, e))
+            })
+            .collect()
+    }
+
+    /// Forgets the calls received so far, keeping the registered responses.
+    pub fn clear_calls(&mut self)
+    {
+        self.calls.clear();
+    }
+
+    fn handle(
+        &mut self,
+        function_type: FunctionType,
+        path: &str,
+        args: BTreeMap<String, ConvexValue>,
+    ) -> anyhow::Result<FunctionResult>
+    {
+        if let Some(functions) = self.functions {
+            let function = functions
+                .iter()
+                .find(|function| function.path == path)
+                .ok_or_else(|| anyhow::anyhow!("'{}' is not a function of this deployment", path))?;
+            if function.visibility == Visibility::Internal {
+                anyhow::bail!("'{}' is an internal function and can't be called by clients", path);
+            }
+            if function.function_type != function_type {
+                anyhow::bail!("'{}' is a {:?}, not a {:?}", path, function.function_type, function_type);
+            }
+        }
+
+        self.calls.push(RecordedCall {
+            path: path.to_string(),
+            function_type,
+            args: args.clone(),
+        });
+
+        let handler = self
+            .handlers
+            .get_mut(path)
+            .ok_or_else(|| anyhow::anyhow!("No mock response registered for '{}'", path))?;
+        handler(args)
+    }
+}
+
+impl ConvexTransport for MockTransport
+{
+    type Subscription = futures::stream::Iter<std::vec::IntoIter<FunctionResult>>;
+
+    /// Yields the current response once, then ends
+    async fn subscribe(&mut self, path: &str, args: BTreeMap<String, ConvexValue>) -> anyhow::Result<Self::Subscription>
+    {
+        let result = self.handle(FunctionType::Query, path, args)?;
+        Ok(futures::stream::iter(vec![result]))
+    }
+
+    async fn query(&mut self, path: &str, args: BTreeMap<String, ConvexValue>) -> anyhow::Result<FunctionResult>
+    {
+        self.handle(FunctionType::Query, path, args)
+    }
+
+    async fn mutation(&mut self, path: &str, args: BTreeMap<String, ConvexValue>) -> anyhow::Result<FunctionResult>
+    {
+        self.handle(FunctionType::Mutation, path, args)
+    }
+
+    async fn action(&mut self, path: &str, args: BTreeMap<String, ConvexValue>) -> anyhow::Result<FunctionResult>
+    {
+        self.handle(FunctionType::Action, path, args)
+    }
+}
+
+/// The result Convex sends for arguments that don't match the function's validators
+fn invalid_arguments(error: impl std::fmt::Display) -> FunctionResult
+{
+    FunctionResult::ErrorMessage(format!("ArgumentValidationError: {}", error))
+}
+
+/// Convert a typed return value into the result Convex would send
+pub(crate) fn encode_output<T: Serialize>(output: &T) -> FunctionResult
+{
+    match serde_json::to_value(output)
+        .map_err(|e| e.to_string())
+        .and_then(|json| from_convex_json(json).map_err(|e| e.to_string()))
+    {
+        Ok(value) => FunctionResult::Value(value),
+        Err(e) => FunctionResult::ErrorMessage(format!("Mock returned an invalid value: {}", e)),
+    }
+}
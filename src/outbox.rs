@@ -0,0 +1,286 @@
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use convex::Value as ConvexValue;
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+
+use crate::client::{ConvexFunctionCall, ConvexTransport};
+use crate::errors::{ConvexCallError, ConvexOutboxError};
+
+/// A call waiting in the outbox.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxEntry<C>
+{
+    /// Identifies the entry within its outbox, in the order entries were pushed
+    pub id: u64,
+    /// The function call, usually the generated `ConvexCall`
+    pub call: C,
+}
+
+/// A call that reached Convex but failed, and was removed from the outbox.
+#[derive(Debug)]
+pub struct OutboxFailure<C>
+{
+    /// The entry that failed
+    pub entry: OutboxEntry<C>,
+    /// Why the call failed. The data of a thrown `ConvexError` is kept in
+    /// [`ConvexCallError::ApplicationError`] and can be decoded with the error type of the function.
+    pub error: ConvexCallError,
+}
+
+/// The outcome of [`Outbox::replay`].
+#[derive(Debug)]
+pub struct ReplayReport<C>
+{
+    /// Entries that were applied, with the value each function returned. Decode it with
+    /// [`from_value`](crate::decode::from_value) into the return type of the function.
+    pub delivered: Vec<(OutboxEntry<C>, ConvexValue)>,
+    /// Entries that Convex rejected. They are not retried.
+    pub failed: Vec<OutboxFailure<C>>,
+    /// The transport error that stopped the replay, if Convex could not be reached. The entry
+    /// being sent and all entries after it are still pending.
+    pub interrupted: Option<ConvexCallError>,
+}
+
+impl<C> Default for ReplayReport<C>
+{
+    fn default() -> Self
+    {
+        Self {
+            delivered: Vec::new(),
+            failed: Vec::new(),
+            interrupted: None,
+        }
+    }
+}
+
+/// A line of the outbox file
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum Record<C>
+{
+    Push
+    {
+        id: u64, call: C
+    },
+    Done
+    {
+        id: u64
+    },
+}
+
+/// A durable queue of function calls, enabled with the `outbox` feature.
+///
+/// The queue holds calls of type `C`, usually the generated `ConvexCall` enum, so calls to different
+/// mutations share one file and are sent in the order they were pushed. Calls are appended to a
+/// local file as they are pushed, so they survive restarts while the app is offline.
+/// [`replay`](Outbox::replay) sends them in order once Convex can be reached again, appending a
+/// record for each entry that no longer needs sending. The file is emptied once the outbox is empty.
+///
+/// Delivery is at least once: the record for an entry is only appended after its call returned,
+/// so a crash in between sends the entry again on the next replay. Mutations queued in an outbox
+/// should be safe to apply twice.
+#[derive(Debug)]
+pub struct Outbox<C>
+{
+    path: PathBuf,
+    pending: VecDeque<OutboxEntry<C>>,
+    next_id: u64,
+}
+
+impl<C> Outbox<C>
+where
+    C: ConvexFunctionCall + Clone + Send,
+{
+    /// Opens the outbox stored at `path`. An outbox that does not exist yet starts empty.
+    ///
+    /// A partially written last line, left by a crash during [`push`](Outbox::push), is dropped.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or contains invalid records, including entries
+    /// for a function that `C` cannot call.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, ConvexOutboxError>
+    {
+        let path = path.into();
+        let mut pending = VecDeque::new();
+        let mut next_id = 1;
+        let mut torn = false;
+
+        if path.exists() {
+            let lines = BufReader::new(File::open(&path)?).lines().collect::<Result<Vec<_>, _>>()?;
+            let last = lines.len();
+
+            for (number, line) in lines.into_iter().enumerate().map(|(i, line)| (i + 1, line)) {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<Record<C>>(&line) {
+                    Ok(Record::Push { id, call }) => {
+                        next_id = next_id.max(id + 1);
+                        pending.push_back(OutboxEntry { id, call });
+                    }
+                    Ok(Record::Done { id }) => pending.retain(|entry| entry.id != id),
+                    // Only a line that isn't even valid JSON can be the result of an interrupted push
+                    Err(_) if number == last && serde_json::from_str::<IgnoredAny>(&line).is_err() => torn = true,
+                    Err(e) => {
+                        return Err(ConvexOutboxError::Corrupt {
+                            line: number,
+                            details: e.to_string(),
+                        })
+                    }
+                }
+            }
+        }
+
+        let outbox = Self { path, pending, next_id };
+        if torn {
+            outbox.rewrite()?;
+        }
+
+        Ok(outbox)
+    }
+
+    /// Appends a call to the outbox and returns its id.
+    ///
+    /// Accepts the generated `{Function}Args` structs directly, through their conversion into
+    /// `ConvexCall`.
+    ///
+    /// # Errors
+    /// Returns an error if the entry cannot be written to the file.
+    pub fn push(&mut self, call: impl Into<C>) -> Result<u64, ConvexOutboxError>
+    {
+        let entry = OutboxEntry {
+            id: self.next_id,
+            call: call.into(),
+        };
+
+        self.append(&push_record(&entry))?;
+        self.next_id += 1;
+        self.pending.push_back(entry);
+
+        Ok(self.next_id - 1)
+    }
+
+    /// The entries that have not been sent yet, oldest first.
+    pub fn pending(&self) -> impl Iterator<Item = &OutboxEntry<C>>
+    {
+        self.pending.iter()
+    }
+
+    /// The number of entries that have not been sent yet.
+    pub fn len(&self) -> usize
+    {
+        self.pending.len()
+    }
+
+    /// Whether every entry has been sent.
+    pub fn is_empty(&self) -> bool
+    {
+        self.pending.is_empty()
+    }
+
+    /// Sends the pending calls in order.
+    ///
+    /// Each entry is removed once Convex has either applied or rejected it. If Convex cannot be
+    /// reached, the replay stops and the remaining entries are kept for the next attempt.
+    ///
+    /// # Errors
+    /// Returns an error if the outbox file cannot be updated.
+    pub async fn replay<T: ConvexTransport + Send>(
+        &mut self,
+        transport: &mut T,
+    ) -> Result<ReplayReport<C>, ConvexOutboxError>
+    {
+        let mut report = ReplayReport::default();
+
+        while let Some(entry) = self.pending.front().cloned() {
+            let result = match entry.call.clone().dispatch(transport).await {
+                Err(ConvexCallError::Client(error)) => {
+                    report.interrupted = Some(ConvexCallError::Client(error));
+                    break;
+                }
+                result => result,
+            };
+
+            self.append(&Record::<C>::Done { id: entry.id })?;
+            self.pending.pop_front();
+
+            match result {
+                Ok(value) => report.delivered.push((entry, value)),
+                Err(error) => report.failed.push(OutboxFailure { entry, error }),
+            }
+        }
+
+        if self.pending.is_empty() {
+            self.rewrite()?;
+        }
+
+        Ok(report)
+    }
+
+    /// Replace the file with just the pending entries.
+    ///
+    /// The entries are written to a sibling file that is renamed over the outbox, so a crash leaves
+    /// either the old or the new file in place and never a truncated one.
+    fn rewrite(&self) -> Result<(), ConvexOutboxError>
+    {
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        let mut file = File::create(&temp_path)?;
+        for entry in &self.pending {
+            let mut line = serde_json::to_string(&push_record(entry))?;
+            line.push('\n');
+            file.write_all(line.as_bytes())?;
+        }
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&temp_path, &self.path)?;
+        sync_parent(&self.path)?;
+
+        Ok(())
+    }
+
+    fn append(&self, record: &Record<C>) -> Result<(), ConvexOutboxError>
+    {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        file.sync_data()?;
+
+        Ok(())
+    }
+}
+
+fn push_record<C: Clone>(entry: &OutboxEntry<C>) -> Record<C>
+{
+    Record::Push {
+        id: entry.id,
+        call: entry.call.clone(),
+    }
+}
+
+/// Persist a rename in the directory containing `path`
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> std::io::Result<()>
+{
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    File::open(parent)?.sync_all()
+}
+
+/// Directories can't be opened and synced on other platforms
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> std::io::Result<()>
+{
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+use crate::client::{ConvexFunctionArgs, Query};
+
+/// The arguments of a paginated query, matching Convex's `paginationOptsValidator`.
+///
+/// https://docs.convex.dev/database/pagination
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginationOpts
+{
+    /// How many items to load in this page. Sent as a float, like every `v.number()`.
+    pub num_items: f64,
+    /// Where to start the page, `None` for the first page
+    pub cursor: Option<String>,
+    /// Where to end the page, to reload a page with the same boundaries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_cursor: Option<String>,
+    /// A soft limit on the documents scanned for this page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum_rows_read: Option<f64>,
+    /// A soft limit on the bytes scanned for this page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum_bytes_read: Option<f64>,
+    /// Set by `usePaginatedQuery` to tell apart the queries of a paginated list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<f64>,
+}
+
+impl PaginationOpts
+{
+    /// Options for the first page, loading `num_items` items.
+    pub fn new(num_items: u32) -> Self
+    {
+        Self {
+            num_items: f64::from(num_items),
+            cursor: None,
+            end_cursor: None,
+            maximum_rows_read: None,
+            maximum_bytes_read: None,
+            id: None,
+        }
+    }
+}
+
+/// A page returned by a paginated query, matching Convex's `paginationResultValidator`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginationResult<T>
+{
+    /// The items in this page
+    pub page: Vec<T>,
+    /// Whether this is the last page
+    pub is_done: bool,
+    /// The cursor to pass to load the next page
+    pub continue_cursor: String,
+    /// A cursor to split this page in two, when Convex recommends doing so
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split_cursor: Option<String>,
+    /// Whether the page read too much data and should be split
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_status: Option<PageStatus>,
+}
+
+/// Why Convex suggests splitting a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PageStatus
+{
+    /// The page is large, and should be split before it grows further
+    SplitRecommended,
+    /// The page is too large to load again, and must be split
+    SplitRequired,
+}
+
+/// Arguments of a query that takes `paginationOpts: paginationOptsValidator`.
+///
+/// Generated args structs implement this for such queries, so they can be passed to
+/// [`ConvexClientExt::paginate`](crate::convex::ConvexClientExt::paginate).
+pub trait PaginatedQuery: ConvexFunctionArgs<Kind = Query>
+{
+    /// The pagination options in the arguments
+    fn pagination_opts(&mut self) -> &mut PaginationOpts;
+}
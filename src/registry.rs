@@ -0,0 +1,49 @@
+/// Whether a Convex function is a query, a mutation or an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FunctionType
+{
+    /// Defined with `query` or `internalQuery`
+    Query,
+    /// Defined with `mutation` or `internalMutation`
+    Mutation,
+    /// Defined with `action` or `internalAction`
+    Action,
+}
+
+/// Whether a Convex function can be called by clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Visibility
+{
+    /// Part of the public `api`
+    Public,
+    /// Only callable from other Convex functions
+    Internal,
+}
+
+/// The runtime a Convex function runs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Runtime
+{
+    /// The default Convex runtime
+    Convex,
+    /// Node.js, for modules starting with a `"use node"` directive
+    Node,
+}
+
+/// A description of a Convex function, as listed in the generated `FUNCTIONS` registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionInfo
+{
+    /// The path of the function, e.g. `"messages:send"`
+    pub path: &'static str,
+    /// Whether the function is a query, a mutation or an action
+    pub function_type: FunctionType,
+    /// Whether the function is public or internal
+    pub visibility: Visibility,
+    /// The runtime the function runs in
+    pub runtime: Runtime,
+    /// The name and validator of each argument, e.g. `("id", "v.id(\"messages\")")`
+    pub args: &'static [(&'static str, &'static str)],
+    /// The validator of the return value, if the function declares one
+    pub returns: Option<&'static str>,
+}
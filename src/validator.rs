@@ -0,0 +1,551 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use convex::Value as ConvexValue;
+use convex_typegen_codegen::convex::{id_table_name, literal_value, validate_type_name};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use crate::client::ConvexFunctionArgs;
+use crate::convex::{ConvexFunction, ConvexTable};
+use crate::decode::value_kind;
+use crate::errors::{ArgumentErrorKind, ConvexArgumentError, ConvexTypeGeneratorError, ConvexValidationError};
+use crate::json::from_convex_json;
+
+/// A runtime mirror of Convex's `v.*` validators.
+///
+/// Validators are built from the parsed schema and function definitions, or by the generated
+/// `validator()` methods, and check values the same way Convex does before a function runs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Validator
+{
+    /// `v.id("table")`
+    Id(String),
+    /// `v.null()`
+    Null,
+    /// `v.int64()`
+    Int64,
+    /// `v.number()`
+    Float64,
+    /// `v.boolean()`
+    Boolean,
+    /// `v.string()`
+    String,
+    /// `v.bytes()`
+    Bytes,
+    /// `v.literal(...)`
+    Literal(ConvexValue),
+    /// `v.array(...)`
+    Array(Box<Validator>),
+    /// `v.object({...})`
+    Object(BTreeMap<String, Validator>),
+    /// `v.record(keys, values)`
+    Record(Box<Validator>, Box<Validator>),
+    /// `v.union(...)`
+    Union(Vec<Validator>),
+    /// `v.optional(...)`, only meaningful as the type of an object field
+    Optional(Box<Validator>),
+    /// `v.any()`
+    Any,
+}
+
+impl Validator
+{
+    /// Builds a validator from a parsed data type, such as [`ConvexColumn::data_type`].
+    ///
+    /// [`ConvexColumn::data_type`]: crate::convex::ConvexColumn::data_type
+    ///
+    /// # Errors
+    /// Returns an error if the data type is not a known Convex type or is missing its inner types.
+    pub fn from_data_type(data_type: &JsonValue) -> Result<Self, ConvexTypeGeneratorError>
+    {
+        let type_name = data_type["type"]
+            .as_str()
+            .ok_or_else(|| ConvexTypeGeneratorError::InvalidSchema {
+                context: "validator".to_string(),
+                details: "Missing type name".to_string(),
+            })?;
+        validate_type_name(type_name)?;
+
+        let invalid = |details: &str| ConvexTypeGeneratorError::InvalidSchema {
+            context: format!("validator.{}", type_name),
+            details: details.to_string(),
+        };
+
+        Ok(match type_name {
+            "id" => Self::Id(
+                id_table_name(data_type)
+                    .ok_or_else(|| invalid("Missing table name"))?
+                    .to_string(),
+            ),
+            "null" => Self::Null,
+            "int64" => Self::Int64,
+            "number" => Self::Float64,
+            "boolean" => Self::Boolean,
+            "string" => Self::String,
+            "bytes" => Self::Bytes,
+            "any" => Self::Any,
+            "literal" => Self::Literal(literal_value(data_type).ok_or_else(|| invalid("Unsupported literal value"))?),
+            "array" => Self::Array(Box::new(Self::from_data_type(&data_type["elements"])?)),
+            "optional" => Self::Optional(Box::new(Self::from_data_type(&data_type["inner"])?)),
+            "record" => Self::Record(
+                Box::new(Self::from_data_type(&data_type["keyType"])?),
+                Box::new(Self::from_data_type(&data_type["valueType"])?),
+            ),
+            "union" => Self::Union(
+                data_type["variants"]
+                    .as_array()
+                    .ok_or_else(|| invalid("Missing union variants"))?
+                    .iter()
+                    .map(Self::from_data_type)
+                    .collect::<Result<_, _>>()?,
+            ),
+            "object" => Self::Object(
+                data_type["properties"]
+                    .as_object()
+                    .map(|props| {
+                        props
+                            .iter()
+                            .map(|(name, prop_type)| Ok((name.clone(), Self::from_data_type(prop_type)?)))
+                            .collect::<Result<_, ConvexTypeGeneratorError>>()
+                    })
+                    .transpose()?
+                    .unwrap_or_default(),
+            ),
+            _ => unreachable!("type name was validated"),
+        })
+    }
+
+    /// Builds the object validator for a function's `args`.
+    ///
+    /// # Errors
+    /// Returns an error if any argument type is invalid.
+    pub fn for_function_args(function: &ConvexFunction) -> Result<Self, ConvexTypeGeneratorError>
+    {
+        let fields = function
+            .params
+            .iter()
+            .map(|param| Ok((param.name.clone(), Self::from_data_type(&param.data_type)?)))
+            .collect::<Result<_, ConvexTypeGeneratorError>>()?;
+        Ok(Self::Object(fields))
+    }
+
+    /// Builds the object validator for the user-defined fields of a table's documents.
+    ///
+    /// # Errors
+    /// Returns an error if any column type is invalid.
+    pub fn for_table(table: &ConvexTable) -> Result<Self, ConvexTypeGeneratorError>
+    {
+        let fields = table
+            .columns
+            .iter()
+            .map(|column| Ok((column.name.clone(), Self::from_data_type(&column.data_type)?)))
+            .collect::<Result<_, ConvexTypeGeneratorError>>()?;
+        Ok(Self::Object(fields))
+    }
+
+    /// Checks a Convex value against this validator.
+    ///
+    /// `v.id(...)` values are only checked to be strings, since the table of an id cannot be
+    /// recovered from the id alone. Use [`Validator::validate_with_ids`] to check table names too.
+    ///
+    /// # Errors
+    /// Returns the first mismatch found, with the path to the offending value.
+    pub fn validate(&self, value: &ConvexValue) -> Result<(), ConvexValidationError>
+    {
+        self.check(value, &|_| None, &mut Vec::new())
+    }
+
+    /// Checks a Convex value against this validator, resolving the table of each id with
+    /// `table_of` so `v.id("table")` can be checked against the table name.
+    ///
+    /// Ids for which `table_of` returns `None` are rejected.
+    ///
+    /// # Errors
+    /// Returns the first mismatch found, with the path to the offending value.
+    pub fn validate_with_ids<F>(&self, value: &ConvexValue, table_of: F) -> Result<(), ConvexValidationError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let table_of = |id: &str| Some(table_of(id));
+        self.check(value, &table_of, &mut Vec::new())
+    }
+
+    /// Serializes a value and checks the result against this validator.
+    ///
+    /// Values are serialized to Convex JSON, so types generated by this crate validate exactly as
+    /// they are sent. This is what the generated `validate()` methods call.
+    ///
+    /// # Errors
+    /// Returns an error if the value cannot be serialized or does not match.
+    pub fn validate_serialized<T: Serialize>(&self, value: &T) -> Result<(), ConvexValidationError>
+    {
+        let value = serde_json::to_value(value)
+            .map_err(|e| e.to_string())
+            .and_then(|json| from_convex_json(json).map_err(|e| e.to_string()))
+            .map_err(|details| ConvexValidationError {
+                path: Vec::new(),
+                expected: self.to_string(),
+                found: format!("unserializable value ({})", details),
+            })?;
+        self.validate(&value)
+    }
+
+    /// `table_of` returns `None` when ids are not being resolved, and `Some(None)` for an unknown id.
+    fn check(
+        &self,
+        value: &ConvexValue,
+        table_of: &dyn Fn(&str) -> Option<Option<String>>,
+        path: &mut Vec<String>,
+    ) -> Result<(), ConvexValidationError>
+    {
+        let mismatch = |found: String, path: &Vec<String>| ConvexValidationError {
+            path: path.clone(),
+            expected: self.to_string(),
+            found,
+        };
+
+        match (self, value) {
+            (Self::Any, _)
+            | (Self::Null, ConvexValue::Null)
+            | (Self::Int64, ConvexValue::Int64(_))
+            | (Self::Float64, ConvexValue::Float64(_))
+            | (Self::Boolean, ConvexValue::Boolean(_))
+            | (Self::String, ConvexValue::String(_))
+            | (Self::Bytes, ConvexValue::Bytes(_)) => Ok(()),
+            (Self::Id(table), ConvexValue::String(id)) => match table_of(id) {
+                None => Ok(()),
+                Some(Some(found)) if &found == table => Ok(()),
+                Some(Some(found)) => Err(mismatch(format!("id for table \"{}\"", found), path)),
+                Some(None) => Err(mismatch("unknown id".to_string(), path)),
+            },
+            (Self::Literal(expected), value) if expected == value => Ok(()),
+            (Self::Optional(inner), value) => inner.check(value, table_of, path),
+            (Self::Array(elements), ConvexValue::Array(items)) => {
+                for (i, item) in items.iter().enumerate() {
+                    path.push(i.to_string());
+                    elements.check(item, table_of, path)?;
+                    path.pop();
+                }
+                Ok(())
+            }
+            (Self::Object(fields), ConvexValue::Object(map)) => {
+                for (name, field) in fields {
+                    path.push(name.clone());
+                    match map.get(name) {
+                        Some(value) => field.check(value, table_of, path)?,
+                        None if matches!(field, Self::Optional(_)) => {}
+                        None => {
+                            return Err(ConvexValidationError {
+                                path: path.clone(),
+                                expected: field.to_string(),
+                                found: "missing field".to_string(),
+                            });
+                        }
+                    }
+                    path.pop();
+                }
+                if let Some(extra) = map.keys().find(|key| !fields.contains_key(*key)) {
+                    path.push(extra.clone());
+                    return Err(ConvexValidationError {
+                        path: path.clone(),
+                        expected: "no field".to_string(),
+                        found: "unknown field".to_string(),
+                    });
+                }
+                Ok(())
+            }
+            (Self::Record(keys, values), ConvexValue::Object(map)) => {
+                for (key, value) in map {
+                    path.push(key.clone());
+                    keys.check(&ConvexValue::String(key.clone()), table_of, path)?;
+                    values.check(value, table_of, path)?;
+                    path.pop();
+                }
+                Ok(())
+            }
+            (Self::Union(variants), value) => {
+                if variants
+                    .iter()
+                    .any(|variant| variant.check(value, table_of, &mut path.clone()).is_ok())
+                {
+                    Ok(())
+                } else {
+                    Err(mismatch(describe_value(value), path))
+                }
+            }
+            (_, value) => Err(mismatch(describe_value(value), path)),
+        }
+    }
+}
+
+/// Describe a value for error messages, showing literal values for scalars
+fn describe_value(value: &ConvexValue) -> String
+{
+    match value {
+        ConvexValue::String(s) => format!("string {:?}", s),
+        ConvexValue::Float64(f) => format!("float64 {}", f),
+        ConvexValue::Int64(i) => format!("int64 {}", i),
+        ConvexValue::Boolean(b) => format!("boolean {}", b),
+        other => value_kind(other).to_string(),
+    }
+}
+
+/// Parse the message Convex returns when it rejects the arguments of a function, such as
+///
+/// ```text
+/// [CONVEX M(users:create)] [Request ID: 8c1e2f] Server Error
+/// ArgumentValidationError: Value does not match validator.
+/// Path: .address.zip
+/// Value: 94107.0
+/// Validator: v.string()
+/// ```
+///
+/// Returns `None` for messages of any other error.
+///
+/// ```
+/// use convex_typegen::errors::ArgumentErrorKind;
+/// use convex_typegen::validator::parse_argument_error;
+///
+/// let message = "ArgumentValidationError: Object is missing the required field `zip`. Consider \
+///                wrapping the field validator in `v.optional(...)` if this is \
+///                expected.\n\nPath: .address\nObject: {city: \"Paris\"}\nValidator: \
+///                v.object({city: v.string(), zip: v.string()})";
+/// let error = parse_argument_error(message).unwrap();
+/// assert_eq!(error.kind, ArgumentErrorKind::MissingField);
+/// assert_eq!(error.path, ["address", "zip"]);
+/// ```
+pub fn parse_argument_error(message: &str) -> Option<ConvexArgumentError>
+{
+    const PREFIX: &str = "ArgumentValidationError: ";
+
+    let details = &message[message.find(PREFIX)? + PREFIX.len()..];
+    let mut lines = details.lines();
+    let summary = lines.next()?;
+    let kind = if summary.starts_with("Value does not match validator") {
+        ArgumentErrorKind::Mismatch
+    } else if summary.starts_with("Object is missing the required field") {
+        ArgumentErrorKind::MissingField
+    } else if summary.starts_with("Object contains extra field") {
+        ArgumentErrorKind::ExtraField
+    } else {
+        return None;
+    };
+
+    let mut path = Vec::new();
+    let mut received = String::new();
+    let mut expected = String::new();
+    for line in lines {
+        let line = line.trim();
+        if let Some(value) = line.strip_prefix("Path: ") {
+            path = parse_argument_path(value);
+        } else if let Some(value) = line.strip_prefix("Value: ").or_else(|| line.strip_prefix("Object: ")) {
+            received = value.to_string();
+        } else if let Some(value) = line.strip_prefix("Validator: ") {
+            expected = value.to_string();
+        }
+    }
+
+    // The path of a missing or extra field points at its object, the field is named in the summary
+    if kind != ArgumentErrorKind::Mismatch {
+        let field = summary.split('`').nth(1)?;
+        path.push(field.to_string());
+    }
+
+    Some(ConvexArgumentError {
+        function_path: function_path_of(message).unwrap_or_default().to_string(),
+        path,
+        rust_path: None,
+        kind,
+        expected,
+        received,
+    })
+}
+
+/// Parse the `ArgumentValidationError` returned for a call made with the generated arguments `A`,
+/// naming the function by its path and the offending argument by its generated Rust field, e.g.
+/// `CreateUserArgs.address.zip`.
+pub fn parse_argument_error_for<A: ConvexFunctionArgs>(message: &str) -> Option<ConvexArgumentError>
+{
+    let mut error = parse_argument_error(message)?;
+    if error.function_path.is_empty() {
+        error.function_path = A::PATH.to_string();
+    }
+
+    let type_name = std::any::type_name::<A>();
+    let mut rust_path = type_name.rsplit("::").next().unwrap_or(type_name).to_string();
+    for (i, segment) in error.path.iter().enumerate() {
+        if segment.parse::<usize>().is_ok() {
+            rust_path.push_str(&format!("[{}]", segment));
+            continue;
+        }
+
+        // Only the arguments themselves are generated as fields, nested objects keep their names
+        let field = A::FIELD_NAMES
+            .iter()
+            .find(|(name, _)| i == 0 && name == segment)
+            .map_or(segment.as_str(), |(_, field)| field);
+        rust_path.push('.');
+        rust_path.push_str(field);
+    }
+    error.rust_path = Some(rust_path);
+
+    Some(error)
+}
+
+/// Split a path such as `.items[0].name` into its field names and array indices
+fn parse_argument_path(path: &str) -> Vec<String>
+{
+    path.split(['.', '['])
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.strip_suffix(']').unwrap_or(segment).to_string())
+        .collect()
+}
+
+/// The function named in the `[CONVEX M(users:create)]` prefix of a Convex error message
+fn function_path_of(message: &str) -> Option<&str>
+{
+    let rest = &message[message.find("[CONVEX ")? + "[CONVEX ".len()..];
+    let rest = &rest[rest.find('(')? + 1..];
+    Some(&rest[..rest.find(")]")?])
+}
+
+impl fmt::Display for Validator
+{
+    /// Formats the validator as the `v.*` expression it mirrors
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            Self::Id(table) => write!(f, "v.id({:?})", table),
+            Self::Null => write!(f, "v.null()"),
+            Self::Int64 => write!(f, "v.int64()"),
+            Self::Float64 => write!(f, "v.number()"),
+            Self::Boolean => write!(f, "v.boolean()"),
+            Self::String => write!(f, "v.string()"),
+            Self::Bytes => write!(f, "v.bytes()"),
+            Self::Any => write!(f, "v.any()"),
+            Self::Literal(ConvexValue::String(s)) => write!(f, "v.literal({:?})", s),
+            Self::Literal(ConvexValue::Int64(i)) => write!(f, "v.literal({}n)", i),
+            Self::Literal(ConvexValue::Float64(n)) => write!(f, "v.literal({})", n),
+            Self::Literal(ConvexValue::Boolean(b)) => write!(f, "v.literal({})", b),
+            Self::Literal(other) => write!(f, "v.literal({:?})", other),
+            Self::Array(elements) => write!(f, "v.array({})", elements),
+            Self::Optional(inner) => write!(f, "v.optional({})", inner),
+            Self::Record(keys, values) => write!(f, "v.record({}, {})", keys, values),
+            Self::Union(variants) => {
+                let variants = variants.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(f, "v.union({})", variants.join(", "))
+            }
+            Self::Object(fields) if fields.is_empty() => write!(f, "v.object({{}})"),
+            Self::Object(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, field)| format!("{}: {}", name, field))
+                    .collect::<Vec<_>>();
+                write!(f, "v.object({{ {} }})", fields.join(", "))
+            }
+        }
+    }
+}
+
+/// Constructors mirroring Convex's `v` builder, used by generated code.
+///
+/// ```
+/// use convex_typegen::validator::v;
+///
+/// let validator = v::object([("name", v::string()), ("age", v::optional(v::number()))]);
+/// assert_eq!(
+///     validator.to_string(),
+///     "v.object({ age: v.optional(v.number()), name: v.string() })"
+/// );
+/// ```
+pub mod v
+{
+    use convex::Value as ConvexValue;
+
+    use super::Validator;
+
+    /// `v.id(table)`
+    pub fn id(table: &str) -> Validator
+    {
+        Validator::Id(table.to_string())
+    }
+
+    /// `v.null()`
+    pub fn null() -> Validator
+    {
+        Validator::Null
+    }
+
+    /// `v.int64()`
+    pub fn int64() -> Validator
+    {
+        Validator::Int64
+    }
+
+    /// `v.number()`
+    pub fn number() -> Validator
+    {
+        Validator::Float64
+    }
+
+    /// `v.boolean()`
+    pub fn boolean() -> Validator
+    {
+        Validator::Boolean
+    }
+
+    /// `v.string()`
+    pub fn string() -> Validator
+    {
+        Validator::String
+    }
+
+    /// `v.bytes()`
+    pub fn bytes() -> Validator
+    {
+        Validator::Bytes
+    }
+
+    /// `v.any()`
+    pub fn any() -> Validator
+    {
+        Validator::Any
+    }
+
+    /// `v.literal(value)`
+    pub fn literal(value: impl Into<ConvexValue>) -> Validator
+    {
+        Validator::Literal(value.into())
+    }
+
+    /// `v.array(elements)`
+    pub fn array(elements: Validator) -> Validator
+    {
+        Validator::Array(Box::new(elements))
+    }
+
+    /// `v.object({ ... })`
+    pub fn object<'a>(fields: impl IntoIterator<Item = (&'a str, Validator)>) -> Validator
+    {
+        Validator::Object(fields.into_iter().map(|(name, field)| (name.to_string(), field)).collect())
+    }
+
+    /// `v.record(keys, values)`
+    pub fn record(keys: Validator, values: Validator) -> Validator
+    {
+        Validator::Record(Box::new(keys), Box::new(values))
+    }
+
+    /// `v.union(...)`
+    pub fn union(variants: impl IntoIterator<Item = Validator>) -> Validator
+    {
+        Validator::Union(variants.into_iter().collect())
+    }
+
+    /// `v.optional(inner)`
+    pub fn optional(inner: Validator) -> Validator
+    {
+        Validator::Optional(Box::new(inner))
+    }
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use common::convex_types::{CountArgs, ListArgs, RecountArgs, SendArgs};
+use convex::FunctionResult;
+use convex_typegen::blocking::BlockingConvexClient;
+use convex_typegen::errors::ConvexCallError;
+use convex_typegen::mock::MockTransport;
+
+#[test]
+fn test_invalid_deployment_url()
+{
+    let result = BlockingConvexClient::new("not a url");
+    assert!(matches!(result, Err(ConvexCallError::Client(_))));
+}
+
+#[test]
+fn test_typed_calls_through_mock_transport()
+{
+    let mut transport = common::convex_types::mock_transport();
+    transport.respond::<CountArgs>(3);
+    transport.respond::<RecountArgs>(4);
+    transport.respond::<SendArgs>("message_1".to_string());
+
+    let mut client = BlockingConvexClient::with_transport(transport).unwrap();
+    assert_eq!(client.query(CountArgs {}).unwrap(), 3);
+    assert_eq!(client.action(RecountArgs {}).unwrap(), 4);
+    assert_eq!(client.mutation(common::send("ada", "hello")).unwrap(), "message_1");
+
+    let sent = client.client().calls_to::<SendArgs>();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].body, "hello");
+}
+
+#[test]
+fn test_subscription_and_errors_through_mock_transport()
+{
+    let mut transport = MockTransport::new();
+    transport.respond::<ListArgs>(vec!["first".to_string(), "second".to_string()]);
+    transport.respond_with::<CountArgs>(FunctionResult::ErrorMessage("Server error".to_string()));
+
+    let mut client = BlockingConvexClient::with_transport(transport).unwrap();
+    let results = client.subscribe(ListArgs {}).unwrap().collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].as_ref().unwrap(), &vec!["first".to_string(), "second".to_string()]);
+
+    assert!(
+        matches!(client.query(CountArgs {}), Err(ConvexCallError::FunctionFailed(message)) if message == "Server error")
+    );
+    assert!(matches!(
+        client.mutation(common::send("ada", "hi")),
+        Err(ConvexCallError::Client(_))
+    ));
+}
@@ -0,0 +1,128 @@
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use tempdir::TempDir;
+
+fn setup_test_env() -> TempDir
+{
+    let temp_dir = TempDir::new("convex_cli_test").expect("Failed to create temp directory");
+
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/server";
+        import { v } from "convex/values";
+
+        export default defineSchema({
+            games: defineTable({
+                win_count: v.number(),
+            }),
+        });
+    "#;
+    let functions_content = r#"
+        import { query } from "./_generated/server";
+        import { v } from "convex/values";
+
+        export const getGame = query({
+            args: { id: v.id("games") },
+            handler: async (ctx, args) => {
+                return await ctx.db.get(args.id);
+            },
+        });
+    "#;
+
+    fs::write(temp_dir.path().join("schema.ts"), schema_content).expect("Failed to write test schema");
+    fs::write(temp_dir.path().join("games.ts"), functions_content).expect("Failed to write test functions");
+
+    temp_dir
+}
+
+fn run(dir: &Path, args: &[&str]) -> Output
+{
+    Command::new(env!("CARGO_BIN_EXE_convex-typegen"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("Failed to run convex-typegen")
+}
+
+#[test]
+fn test_generate_writes_out_file()
+{
+    let temp_dir = setup_test_env();
+    let output = run(
+        temp_dir.path(),
+        &[
+            "generate",
+            "--schema-path",
+            "schema.ts",
+            "--function",
+            "games.ts",
+            "--out-file",
+            "types.rs",
+        ],
+    );
+
+    assert!(output.status.success());
+    let generated_code = fs::read_to_string(temp_dir.path().join("types.rs")).unwrap();
+    assert!(generated_code.contains("pub struct GamesTable"));
+    assert!(generated_code.contains("pub struct GetGameArgs"));
+}
+
+#[test]
+fn test_check_compares_out_file()
+{
+    let temp_dir = setup_test_env();
+    let check = ["check", "--schema-path", "schema.ts", "--out-file", "types.rs"];
+    let generate = ["generate", "--schema-path", "schema.ts", "--out-file", "types.rs"];
+
+    let stale = run(temp_dir.path(), &check);
+    assert_eq!(stale.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&stale.stderr).contains("+pub struct GamesTable"));
+    assert!(!temp_dir.path().join("types.rs").exists());
+
+    assert!(run(temp_dir.path(), &generate).status.success());
+    let fresh = run(temp_dir.path(), &check);
+    assert!(fresh.status.success());
+    assert!(String::from_utf8_lossy(&fresh.stderr).contains("types.rs is up to date"));
+}
+
+#[test]
+fn test_print_outputs_parsed_json()
+{
+    let temp_dir = setup_test_env();
+    let output = run(
+        temp_dir.path(),
+        &["print", "--schema-path", "schema.ts", "--function", "games.ts"],
+    );
+
+    assert!(output.status.success());
+    let printed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(printed["schema"]["tables"][0]["name"], "games");
+    assert_eq!(printed["functions"][0]["name"], "getGame");
+    assert_eq!(printed["functions"][0]["file_name"], "games");
+
+    let output = run(
+        temp_dir.path(),
+        &["print", "functions", "--function", "games.ts", "-s", "schema.ts"],
+    );
+    let printed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(printed[0]["type_"], "query");
+}
+
+#[test]
+fn test_exit_codes()
+{
+    let temp_dir = setup_test_env();
+
+    assert!(run(temp_dir.path(), &["validate", "--schema-path", "schema.ts"])
+        .status
+        .success());
+
+    let missing = run(temp_dir.path(), &["validate", "--schema-path", "missing.ts"]);
+    assert_eq!(missing.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("Schema file not found"));
+
+    assert_eq!(run(temp_dir.path(), &["frobnicate"]).status.code(), Some(2));
+}
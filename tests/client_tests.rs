@@ -0,0 +1,55 @@
+mod common;
+
+use common::convex_types::CountArgs;
+use convex::{FunctionResult, Value as ConvexValue};
+use convex_typegen::client::{watch_many, TypedSubscription};
+use convex_typegen::errors::ConvexCallError;
+use futures::executor::block_on;
+use futures::stream::{self, StreamExt};
+
+fn count(n: i64) -> FunctionResult
+{
+    FunctionResult::Value(ConvexValue::Int64(n))
+}
+
+#[test]
+fn test_subscription_skips_identical_results()
+{
+    let results = stream::iter(vec![count(1), count(1), count(2), count(2), count(1)]);
+    let subscription = TypedSubscription::<CountArgs, _>::new(results);
+
+    let decoded: Vec<i64> = block_on(subscription.map(|result| result.unwrap()).collect());
+    assert_eq!(decoded, vec![1, 2, 1]);
+}
+
+#[test]
+fn test_subscription_decode_and_function_errors()
+{
+    let results = stream::iter(vec![
+        FunctionResult::Value(ConvexValue::String("oops".to_string())),
+        FunctionResult::ErrorMessage("Server error".to_string()),
+    ]);
+    let subscription = TypedSubscription::<CountArgs, _>::new(results);
+
+    let decoded = block_on(subscription.collect::<Vec<_>>());
+    assert!(matches!(decoded[0], Err(ConvexCallError::Decode(_))));
+    assert!(matches!(&decoded[1], Err(ConvexCallError::FunctionFailed(message)) if message == "Server error"));
+}
+
+#[test]
+fn test_watch_many_yields_snapshots()
+{
+    let first =
+        TypedSubscription::<CountArgs, _>::new(stream::iter(vec![count(1), count(1), count(3)]).chain(stream::pending()));
+    let second = TypedSubscription::<CountArgs, _>::new(stream::iter(vec![count(10)]).chain(stream::pending()));
+
+    let snapshots: Vec<Vec<i64>> = block_on(
+        watch_many([first, second])
+            .map(|snapshot| snapshot.into_iter().map(Result::unwrap).collect())
+            .take(1)
+            .collect(),
+    );
+
+    // Both streams were ready at once, so the first snapshot already has the latest values
+    assert_eq!(snapshots, vec![vec![3, 10]]);
+}
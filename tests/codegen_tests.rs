@@ -1,7 +1,8 @@
 use std::fs;
 use std::path::PathBuf;
 
-use convex_typegen::{generate, Configuration};
+use convex_typegen::errors::ConvexTypeGeneratorError;
+use convex_typegen::{check, generate, generate_to_string, Configuration};
 use tempdir::TempDir;
 
 fn setup_test_env(schema_content: &str) -> (TempDir, PathBuf, PathBuf)
@@ -52,4 +53,696 @@ fn test_generated_types()
     assert!(generated_code.contains("pub isActive: bool"));
     assert!(generated_code.contains("pub tags: Vec<String>"));
     assert!(generated_code.contains("pub metadata: std::collections::BTreeMap<String, f64>"));
+    assert!(generated_code.contains("pub fn validate(&self)"));
+    assert!(generated_code.contains(r#"("tags", v::array(v::string()))"#));
+}
+
+#[test]
+fn test_generated_code_can_be_included()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/schema";
+
+        export default defineSchema({
+            users: defineTable({
+                displayName: v.string(),
+            }),
+        });
+    "#;
+
+    let (temp_dir, schema_path, output_path) = setup_test_env(schema_content);
+    let config = Configuration {
+        schema_path,
+        out_file: output_path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    assert!(generate(config).is_ok());
+
+    // `include!` rejects inner attributes, and top-level imports could clash with the includer's
+    let generated_code = fs::read_to_string(output_path).expect("Failed to read generated code");
+    assert!(!generated_code.contains("#!["));
+    assert!(!generated_code.lines().any(|line| line.starts_with("use ")));
+    assert!(generated_code.contains(
+        "#[allow(dead_code, non_camel_case_types, non_snake_case)]\n#[derive(Debug, Clone, serde::Serialize, \
+         serde::Deserialize)]\npub struct UsersTable {"
+    ));
+
+    // In `OUT_DIR` mode only the file name of `out_file` is kept
+    let config = Configuration {
+        out_file: "src/convex_types.rs".to_string(),
+        out_dir: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        config.output_path_in(Some(temp_dir.path().as_os_str())).unwrap(),
+        temp_dir.path().join("convex_types.rs")
+    );
+    assert!(matches!(
+        config.output_path_in(None),
+        Err(ConvexTypeGeneratorError::MissingOutDir)
+    ));
+}
+
+#[test]
+fn test_check_detects_stale_output()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/schema";
+
+        export default defineSchema({
+            users: defineTable({
+                name: v.string(),
+            }),
+        });
+    "#;
+
+    let (_temp_dir, schema_path, output_path) = setup_test_env(schema_content);
+    let config = Configuration {
+        schema_path: schema_path.clone(),
+        out_file: output_path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    // A missing output file is out of date too
+    assert!(matches!(
+        check(config.clone()),
+        Err(ConvexTypeGeneratorError::OutOfDate { .. })
+    ));
+
+    assert!(generate(config.clone()).is_ok());
+    assert!(check(config.clone()).is_ok());
+
+    fs::write(
+        &schema_path,
+        schema_content.replace("name: v.string()", "name: v.string(), age: v.number()"),
+    )
+    .unwrap();
+    let before = fs::read_to_string(&output_path).unwrap();
+    match check(config) {
+        Err(ConvexTypeGeneratorError::OutOfDate { file, diff }) => {
+            assert_eq!(file, output_path.to_string_lossy());
+            assert!(diff.contains("+    pub age: f64,"));
+            assert!(diff.contains("@@"));
+        }
+        other => panic!("Expected OutOfDate error, got {:?}", other),
+    }
+    assert_eq!(fs::read_to_string(&output_path).unwrap(), before);
+}
+
+#[test]
+fn test_generate_to_string_from_memory()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/server";
+        import { v } from "convex/values";
+
+        export default defineSchema({
+            messages: defineTable({
+                body: v.string(),
+            }),
+        });
+    "#;
+    let functions_content = r#"
+        import { mutation } from "./_generated/server";
+        import { v } from "convex/values";
+
+        export const send = mutation({
+            args: { body: v.string() },
+            handler: async (ctx, args) => {
+                await ctx.db.insert("messages", args);
+            },
+        });
+    "#;
+
+    // Neither file exists on disk
+    let config = Configuration {
+        schema_path: PathBuf::from("virtual/convex/schema.ts"),
+        function_paths: vec![PathBuf::from("virtual/convex/chat/messages.ts")],
+        sources: [
+            (PathBuf::from("virtual/convex/schema.ts"), schema_content.to_string()),
+            (
+                PathBuf::from("virtual/convex/chat/messages.ts"),
+                functions_content.to_string(),
+            ),
+        ]
+        .into(),
+        ..Default::default()
+    };
+
+    let generated_code = generate_to_string(config.clone()).expect("Failed to generate code from memory");
+    assert!(generated_code.contains("pub struct MessagesTable"));
+    assert!(generated_code.contains("pub struct SendArgs"));
+    assert!(generated_code.contains("\"chat/messages:send\""));
+
+    let config = Configuration {
+        sources: Default::default(),
+        ..config
+    };
+    assert!(matches!(
+        generate_to_string(config),
+        Err(ConvexTypeGeneratorError::MissingSchemaFile)
+    ));
+}
+
+#[test]
+fn test_generate_from_memory_and_disk()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/server";
+        import { v } from "convex/values";
+
+        export default defineSchema({
+            messages: defineTable({
+                body: v.string(),
+            }),
+        });
+    "#;
+    let function_content = |name: &str| {
+        format!(
+            r#"
+            import {{ query }} from "./_generated/server";
+
+            export const {} = query({{
+                args: {{}},
+                handler: async (ctx) => null,
+            }});
+        "#,
+            name
+        )
+    };
+
+    // The schema and one function file exist on disk, the other function file only in memory
+    let (temp_dir, schema_path, _) = setup_test_env(schema_content);
+    fs::create_dir(temp_dir.path().join("chat")).unwrap();
+    fs::write(temp_dir.path().join("chat/list.ts"), function_content("list")).unwrap();
+
+    let config = Configuration {
+        schema_path,
+        function_paths: vec![
+            temp_dir.path().join("chat/../chat/list.ts"),
+            temp_dir.path().join("./admin/stats.ts"),
+        ],
+        sources: [(temp_dir.path().join("admin/stats.ts"), function_content("stats"))].into(),
+        ..Default::default()
+    };
+
+    let generated_code = generate_to_string(config.clone()).expect("Failed to generate code");
+    assert!(generated_code.contains("\"chat/list:list\""));
+    assert!(generated_code.contains("\"admin/stats:stats\""));
+
+    // Functions are named relative to the schema's directory, so files outside of it are rejected
+    let outside = temp_dir.path().join("../other.ts");
+    let config = Configuration {
+        function_paths: vec![outside.clone()],
+        sources: [(outside, function_content("other"))].into(),
+        ..config
+    };
+    assert!(matches!(
+        generate_to_string(config),
+        Err(ConvexTypeGeneratorError::InvalidPath(message)) if message.contains("other.ts")
+    ));
+}
+
+#[test]
+fn test_configuration_builder()
+{
+    let config = Configuration::default()
+        .with_schema_path("app/convex/schema.ts")
+        .with_out_file("src/types.rs")
+        .with_out_dir(true)
+        .with_function_paths(["app/convex/games.ts", "app/convex/admin/users.ts"])
+        .with_rerun_if_changed(true)
+        .with_source("app/convex/games.ts", "export {};")
+        .with_source("app/convex/schema.ts", "export default {};");
+
+    assert_eq!(config.schema_path, PathBuf::from("app/convex/schema.ts"));
+    assert_eq!(config.out_file, "src/types.rs");
+    assert!(config.out_dir);
+    assert_eq!(
+        config.function_paths,
+        vec![
+            PathBuf::from("app/convex/games.ts"),
+            PathBuf::from("app/convex/admin/users.ts")
+        ]
+    );
+    assert!(config.rerun_if_changed);
+    assert_eq!(config.sources.len(), 2);
+    assert_eq!(config.sources[&PathBuf::from("app/convex/games.ts")], "export {};");
+
+    // Replacing the function paths keeps the other settings
+    let config = config.with_function_paths(["app/convex/chat.ts"]);
+    assert_eq!(config.function_paths, vec![PathBuf::from("app/convex/chat.ts")]);
+    assert_eq!(config.out_file, "src/types.rs");
+}
+
+#[test]
+fn test_unvalidatable_field_fails_generation()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/schema";
+
+        export default defineSchema({
+            tasks: defineTable({
+                title: v.string(),
+                status: v.literal(DEFAULT_STATUS),
+            }),
+        });
+    "#;
+
+    let config = Configuration {
+        schema_path: PathBuf::from("convex/schema.ts"),
+        sources: [(PathBuf::from("convex/schema.ts"), schema_content.to_string())].into(),
+        ..Default::default()
+    };
+
+    match generate_to_string(config) {
+        Err(ConvexTypeGeneratorError::InvalidSchema { context, .. }) => assert_eq!(context, "tasks.status"),
+        other => panic!("Expected InvalidSchema error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_generated_code_is_valid_rust()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/schema";
+
+        export default defineSchema({
+            tasks: defineTable({
+                type: v.string(),
+                kind: v.literal("draft"),
+                priority: v.optional(v.literal(1n)),
+                status: v.union(v.literal("in-progress"), v.literal("in_progress"), v.literal("2fa")),
+            }),
+        });
+    "#;
+
+    let (_temp_dir, schema_path, output_path) = setup_test_env(schema_content);
+    let config = Configuration {
+        schema_path,
+        out_file: output_path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    assert!(generate(config).is_ok());
+
+    let generated_code = fs::read_to_string(output_path).expect("Failed to read generated code");
+    assert!(generated_code.contains("pub r#type: String"));
+    assert!(generated_code.contains("pub kind: String"));
+    assert!(generated_code.contains("pub priority: Option<i64>"));
+    assert!(generated_code.contains("    InProgress,\n"));
+    assert!(generated_code.contains("    InProgress_,\n"));
+    assert!(generated_code.contains("    V2fa,\n"));
+}
+
+#[test]
+fn test_invalid_generated_code_names_schema_element()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/schema";
+
+        export default defineSchema({
+            user$settings: defineTable({
+                theme: v.string(),
+            }),
+        });
+    "#;
+
+    let (_temp_dir, schema_path, output_path) = setup_test_env(schema_content);
+    let config = Configuration {
+        schema_path,
+        out_file: output_path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    match generate(config) {
+        Err(ConvexTypeGeneratorError::InvalidGeneratedCode { element, details }) => {
+            assert_eq!(element, "table `user$settings`");
+            assert_eq!(details, "`User$settingsTable` is not a valid identifier");
+        }
+        other => panic!("Expected InvalidGeneratedCode error, got {:?}", other),
+    }
+    assert!(!output_path.exists());
+}
+
+#[test]
+fn test_generated_function_client_types()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/schema";
+
+        export default defineSchema({
+            posts: defineTable({
+                title: v.string(),
+            }),
+        });
+    "#;
+
+    let (temp_dir, schema_path, output_path) = setup_test_env(schema_content);
+    let function_path = temp_dir.path().join("posts.ts");
+    fs::write(
+        &function_path,
+        r#"
+        import { query, internalMutation, action } from "./_generated/server";
+        import { v } from "convex/values";
+
+        export const get = query({
+            args: { id: v.id("posts") },
+            returns: v.union(v.object({ title: v.string() }), v.null()),
+            handler: async (ctx, args) => null,
+        });
+
+        export const rename = internalMutation({
+            args: { id: v.id("posts"), title: v.string() },
+            returns: v.null(),
+            handler: async (ctx, args) => null,
+        });
+
+        export const sync = action({
+            args: {},
+            handler: async (ctx, args) => null,
+        });
+    "#,
+    )
+    .expect("Failed to write test functions");
+
+    let config = Configuration {
+        schema_path,
+        function_paths: vec![function_path],
+        out_file: output_path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    assert!(generate(config).is_ok());
+
+    let generated_code = fs::read_to_string(output_path).expect("Failed to read generated code");
+    assert!(generated_code.contains("pub struct GetReturnObject"));
+    assert!(generated_code.contains("pub type GetReturn = Option<GetReturnObject>;"));
+    assert!(generated_code.contains("pub type RenameReturn = ();"));
+    assert!(generated_code.contains("pub type SyncReturn = serde_json::Value;"));
+    assert!(generated_code.contains("impl convex_typegen::client::ConvexFunctionArgs for GetArgs"));
+    assert!(generated_code.contains("impl TryFrom<GetArgs> for std::collections::BTreeMap<String, serde_json::Value>"));
+    assert!(!generated_code.contains(".unwrap()"));
+    assert!(generated_code.contains("type Kind = convex_typegen::client::Query;"));
+    assert!(generated_code.contains("type Kind = convex_typegen::client::Mutation;"));
+    assert!(generated_code.contains("type Kind = convex_typegen::client::Action;"));
+
+    // Internal functions can't be called by clients, so they have no `ConvexCall` variant
+    assert!(generated_code.contains("#[serde(tag = \"path\", content = \"args\")]\npub enum ConvexCall {"));
+    assert!(generated_code.contains("#[serde(rename = \"posts:get\")]\n    Get(GetArgs),"));
+    assert!(generated_code.contains("Self::Sync(args) => client.call_function(args).await,"));
+    assert!(generated_code.contains("pub async fn dispatch<C>("));
+    assert!(generated_code.contains("C: convex_typegen::client::ConvexTransport + Send,"));
+    assert!(!generated_code.contains("Rename(RenameArgs)"));
+    assert!(generated_code.contains("impl From<GetArgs> for ConvexCall"));
+    assert!(generated_code.contains("impl convex_typegen::client::ConvexFunctionCall for ConvexCall {"));
+}
+
+#[test]
+fn test_generated_api_modules()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/schema";
+
+        export default defineSchema({
+            messages: defineTable({
+                body: v.string(),
+            }),
+        });
+    "#;
+
+    let (temp_dir, schema_path, output_path) = setup_test_env(schema_content);
+    let messages_path = temp_dir.path().join("messages.ts");
+    fs::write(
+        &messages_path,
+        r#"
+        import { mutation } from "./_generated/server";
+        import { v } from "convex/values";
+
+        export const send = mutation({
+            args: { body: v.string() },
+            handler: async (ctx, args) => null,
+        });
+    "#,
+    )
+    .expect("Failed to write test functions");
+
+    fs::create_dir(temp_dir.path().join("admin")).expect("Failed to create module directory");
+    let admin_path = temp_dir.path().join("admin").join("messages.ts");
+    fs::write(
+        &admin_path,
+        r#"
+        import { internalMutation } from "../_generated/server";
+        import { v } from "convex/values";
+
+        export const send = internalMutation({
+            args: { body: v.string(), urgent: v.boolean() },
+            handler: async (ctx, args) => null,
+        });
+    "#,
+    )
+    .expect("Failed to write test functions");
+
+    let config = Configuration {
+        schema_path,
+        function_paths: vec![messages_path, admin_path],
+        out_file: output_path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    assert!(generate(config).is_ok());
+
+    let generated_code = fs::read_to_string(output_path).expect("Failed to read generated code");
+
+    // Both modules export `send`, so their types are prefixed with the module path
+    assert!(generated_code.contains("pub struct MessagesSendArgs"));
+    assert!(generated_code.contains("pub struct AdminMessagesSendArgs"));
+    assert!(generated_code.contains(r#"pub const FUNCTION_PATH: &'static str = "admin/messages:send";"#));
+
+    assert!(generated_code.contains("pub mod api {"));
+    assert!(generated_code.contains("pub mod internal {"));
+    assert!(generated_code.contains(
+        "pub const send: FunctionReference<\n            super::super::MessagesSendArgs,\n            \
+         super::super::MessagesSendReturn,\n            convex_typegen::client::Mutation,\n        > = \
+         FunctionReference::new(\"messages:send\");"
+    ));
+    assert!(generated_code.contains(r#"FunctionReference::new("admin/messages:send");"#));
+}
+
+#[test]
+fn test_generated_function_registry()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/schema";
+
+        export default defineSchema({
+            files: defineTable({
+                name: v.string(),
+            }),
+        });
+    "#;
+
+    let (temp_dir, schema_path, output_path) = setup_test_env(schema_content);
+    let function_path = temp_dir.path().join("files.ts");
+    fs::write(
+        &function_path,
+        r#"
+        "use node";
+        import { internalAction } from "./_generated/server";
+        import { v } from "convex/values";
+
+        export const resize = internalAction({
+            args: { id: v.id("files"), width: v.optional(v.number()) },
+            returns: v.string(),
+            handler: async (ctx, args) => "",
+        });
+    "#,
+    )
+    .expect("Failed to write test functions");
+
+    let config = Configuration {
+        schema_path,
+        function_paths: vec![function_path],
+        out_file: output_path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    assert!(generate(config).is_ok());
+
+    let generated_code = fs::read_to_string(output_path).expect("Failed to read generated code");
+    assert!(generated_code.contains("pub static FUNCTIONS: &[convex_typegen::registry::FunctionInfo] = &["));
+    assert!(generated_code.contains(r#"path: "files:resize","#));
+    assert!(generated_code.contains("function_type: convex_typegen::registry::FunctionType::Action,"));
+    assert!(generated_code.contains("visibility: convex_typegen::registry::Visibility::Internal,"));
+    assert!(generated_code.contains("runtime: convex_typegen::registry::Runtime::Node,"));
+    assert!(generated_code.contains(r#"args: &[("id", "v.id(\"files\")"), ("width", "v.optional(v.number())")],"#));
+    assert!(generated_code.contains(r#"returns: Some("v.string()"),"#));
+    assert!(generated_code.contains("pub fn mock_transport() -> convex_typegen::mock::MockTransport"));
+}
+
+#[test]
+fn test_generated_pagination_types()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/schema";
+
+        export default defineSchema({
+            messages: defineTable({
+                body: v.string(),
+            }),
+        });
+    "#;
+
+    let (temp_dir, schema_path, output_path) = setup_test_env(schema_content);
+    let function_path = temp_dir.path().join("messages.ts");
+    fs::write(
+        &function_path,
+        r#"
+        import { query } from "./_generated/server";
+        import { paginationOptsValidator, paginationResultValidator } from "convex/server";
+        import { v } from "convex/values";
+
+        export const list = query({
+            args: { paginationOpts: paginationOptsValidator },
+            handler: async (ctx, args) => null,
+        });
+
+        export const search = query({
+            args: { paginationOpts: paginationOptsValidator, text: v.string() },
+            returns: paginationResultValidator(v.object({ body: v.string() })),
+            handler: async (ctx, args) => null,
+        });
+    "#,
+    )
+    .expect("Failed to write test functions");
+
+    let config = Configuration {
+        schema_path,
+        function_paths: vec![function_path],
+        out_file: output_path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    assert!(generate(config).is_ok());
+
+    let generated_code = fs::read_to_string(output_path).expect("Failed to read generated code");
+    assert!(generated_code.contains("    pub paginationOpts: convex_typegen::pagination::PaginationOpts,\n"));
+    assert!(
+        generated_code.contains("pub type ListReturn = convex_typegen::pagination::PaginationResult<serde_json::Value>;")
+    );
+    assert!(generated_code.contains("pub struct SearchReturnItem {\n    pub body: String,\n}"));
+    assert!(
+        generated_code.contains("pub type SearchReturn = convex_typegen::pagination::PaginationResult<SearchReturnItem>;")
+    );
+    assert!(generated_code.contains("impl convex_typegen::pagination::PaginatedQuery for SearchArgs {"));
+    assert!(generated_code.contains(r#"args: &[("paginationOpts", "paginationOptsValidator")],"#));
+}
+
+#[test]
+fn test_generated_argument_field_names()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/schema";
+
+        export default defineSchema({
+            messages: defineTable({
+                body: v.string(),
+            }),
+        });
+    "#;
+
+    let (temp_dir, schema_path, output_path) = setup_test_env(schema_content);
+    let function_path = temp_dir.path().join("messages.ts");
+    fs::write(
+        &function_path,
+        r#"
+        import { mutation } from "./_generated/server";
+        import { v } from "convex/values";
+
+        export const send = mutation({
+            args: { type: v.string(), body: v.string() },
+            handler: async (ctx, args) => null,
+        });
+    "#,
+    )
+    .expect("Failed to write test functions");
+
+    let config = Configuration {
+        schema_path,
+        function_paths: vec![function_path],
+        out_file: output_path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    assert!(generate(config).is_ok());
+
+    let generated_code = fs::read_to_string(output_path).expect("Failed to read generated code");
+    assert!(generated_code.contains("    #[serde(rename = \"type\")]\n    pub r#type: String,\n"));
+    assert!(generated_code.contains("    pub body: String,\n"));
+    assert!(generated_code
+        .contains(r#"    const FIELD_NAMES: &'static [(&'static str, &'static str)] = &[("type", "r#type")];"#));
+}
+
+#[test]
+fn test_generated_error_types()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/schema";
+
+        export default defineSchema({
+            orders: defineTable({
+                total: v.number(),
+            }),
+        });
+    "#;
+
+    let (temp_dir, schema_path, output_path) = setup_test_env(schema_content);
+    let function_path = temp_dir.path().join("orders.ts");
+    fs::write(
+        &function_path,
+        r#"
+        import { mutation, query } from "./_generated/server";
+        import { ConvexError, v } from "convex/values";
+
+        export const cancel = mutation({
+            args: { id: v.id("orders"), attempts: v.int64() },
+            handler: async (ctx, args) => {
+                if (!args.id) throw new ConvexError({ code: "NOT_FOUND", id: args.id });
+                if (args.attempts > 3n) throw new ConvexError({ code: "NOT_FOUND", id: args.id, attempts: args.attempts });
+                if (ctx.locked) throw new ConvexError({ code: "LOCKED" });
+                throw new ConvexError("Try again later");
+            },
+        });
+
+        export const get = query({
+            args: {},
+            handler: async (ctx, args) => null,
+        });
+    "#,
+    )
+    .expect("Failed to write test functions");
+
+    let config = Configuration {
+        schema_path,
+        function_paths: vec![function_path],
+        out_file: output_path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    assert!(generate(config).is_ok());
+
+    let generated_code = fs::read_to_string(output_path).expect("Failed to read generated code");
+    assert!(generated_code.contains("#[serde(tag = \"code\")]\npub enum CancelError {"));
+    assert!(generated_code.contains(
+        "    #[serde(rename = \"NOT_FOUND\")]\n    NotFound {\n        id: String,\n        #[serde(\n            \
+         default,\n            skip_serializing_if = \"Option::is_none\",\n            with = \
+         \"convex_typegen::json::int64::option\"\n        )]\n        attempts: Option<i64>,\n    },\n"
+    ));
+    assert!(generated_code.contains("    #[serde(rename = \"LOCKED\")]\n    Locked,\n"));
+    assert!(generated_code.contains("    #[serde(untagged)]\n    Message(String),\n"));
+    assert!(generated_code.contains("    #[serde(untagged)]\n    Other(serde_json::Value),\n}"));
+    assert!(generated_code
+        .contains("impl convex_typegen::client::ConvexFunctionErrors for CancelArgs {\n    type Error = CancelError;"));
+    assert!(generated_code.contains("pub type GetError = serde_json::Value;"));
 }
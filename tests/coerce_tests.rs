@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+use convex::Value as ConvexValue;
+use convex_typegen::coerce::{coerce_args, coerce_value};
+use convex_typegen::convex::ConvexFunction;
+use convex_typegen::parse_functions;
+use serde_json::{json, Value as JsonValue};
+use tempdir::TempDir;
+
+fn parse_function(source: &str) -> ConvexFunction
+{
+    let temp_dir = TempDir::new("convex_coerce_test").expect("Failed to create temp directory");
+    let function_path = temp_dir.path().join("uploads.ts");
+    fs::write(&function_path, source).unwrap();
+
+    parse_functions(vec![function_path]).unwrap().remove(0)
+}
+
+fn args(value: JsonValue) -> BTreeMap<String, JsonValue>
+{
+    match value {
+        JsonValue::Object(map) => map.into_iter().collect(),
+        other => panic!("Expected object, got {}", other),
+    }
+}
+
+const UPLOAD_FUNCTION: &str = r#"
+import { mutation } from "./_generated/server";
+import { v } from "convex/values";
+
+export const create = mutation({
+    args: {
+        size: v.int64(),
+        ratio: v.number(),
+        data: v.bytes(),
+        kind: v.union(v.literal("image"), v.literal("video")),
+        note: v.optional(v.string()),
+        meta: v.any(),
+        tags: v.array(v.object({ name: v.string(), weight: v.number() })),
+    },
+    handler: async (ctx, args) => {},
+});
+"#;
+
+#[test]
+fn test_coerce_args_by_validator()
+{
+    let function = parse_function(UPLOAD_FUNCTION);
+    let coerced = coerce_args(
+        &function,
+        args(json!({
+            "size": 10,
+            "ratio": 2,
+            "data": "AQID",
+            "kind": "video",
+            "note": null,
+            "meta": { "count": 3 },
+            "tags": [{ "name": "a", "weight": 1 }],
+        })),
+    )
+    .unwrap();
+
+    assert_eq!(coerced["size"], ConvexValue::Int64(10));
+    assert_eq!(coerced["ratio"], ConvexValue::Float64(2.0));
+    assert_eq!(coerced["data"], ConvexValue::Bytes(vec![1, 2, 3]));
+    assert_eq!(coerced["kind"], ConvexValue::String("video".to_string()));
+    assert!(!coerced.contains_key("note"));
+    assert_eq!(
+        coerced["meta"],
+        ConvexValue::Object(BTreeMap::from([("count".to_string(), ConvexValue::Float64(3.0))]))
+    );
+}
+
+#[test]
+fn test_coerce_error_has_path()
+{
+    let function = parse_function(UPLOAD_FUNCTION);
+    let err = coerce_args(
+        &function,
+        args(json!({
+            "size": "10",
+            "ratio": 2,
+            "data": [1, 2],
+            "kind": "image",
+            "meta": null,
+            "tags": [{ "name": "a", "weight": 1 }, { "name": 5, "weight": 1 }],
+        })),
+    )
+    .unwrap_err();
+
+    assert_eq!(err.path, vec!["tags", "1", "name"]);
+    assert_eq!(err.expected, "v.string()");
+    assert_eq!(err.found, "number");
+}
+
+#[test]
+fn test_coerce_rejects_missing_and_unknown_fields()
+{
+    let function = parse_function(UPLOAD_FUNCTION);
+
+    let err = coerce_args(&function, args(json!({ "ratio": 1 }))).unwrap_err();
+    assert_eq!(err.path, vec!["size"]);
+
+    let err = coerce_args(
+        &function,
+        args(json!({
+            "size": 1, "ratio": 1, "data": "", "kind": "image", "meta": 1, "tags": [], "extra": true,
+        })),
+    )
+    .unwrap_err();
+    assert_eq!(err.path, vec!["extra"]);
+}
+
+#[test]
+fn test_coerce_literal_mismatch()
+{
+    let function = parse_function(UPLOAD_FUNCTION);
+    let kind = &function.params.iter().find(|p| p.name == "kind").unwrap().data_type;
+
+    let err = coerce_value(json!("audio"), kind).unwrap_err();
+    assert_eq!(err.expected, r#"v.union(v.literal("image"), v.literal("video"))"#);
+}
@@ -0,0 +1,19 @@
+import { v } from "convex/values";
+import { api } from "./_generated/api";
+import { action, query } from "./_generated/server";
+
+export const count = query({
+    args: {},
+    returns: v.int64(),
+    handler: async (ctx) => {
+        return BigInt((await ctx.db.query("messages").collect()).length);
+    },
+});
+
+export const recount = action({
+    args: {},
+    returns: v.int64(),
+    handler: async (ctx): Promise<bigint> => {
+        return await ctx.runQuery(api.counter.count, {});
+    },
+});
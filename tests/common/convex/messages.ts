@@ -0,0 +1,37 @@
+import { ConvexError, v } from "convex/values";
+import { internalMutation, mutation, query } from "./_generated/server";
+
+export const send = mutation({
+    args: { author: v.id("users"), body: v.string(), type: v.optional(v.string()) },
+    returns: v.id("messages"),
+    handler: async (ctx, args) => {
+        if (args.body.length > 280) {
+            throw new ConvexError({ code: "TOO_LONG", limit: 280 });
+        }
+        if (args.body.trim() === "") {
+            throw new ConvexError("Message is empty");
+        }
+        return await ctx.db.insert("messages", { author: args.author, channel: "general", body: args.body });
+    },
+});
+
+export const list = query({
+    args: {},
+    returns: v.array(v.string()),
+    handler: async (ctx) => {
+        const messages = await ctx.db
+            .query("messages")
+            .withIndex("by_channel", (q) => q.eq("channel", "general"))
+            .collect();
+        return messages.map((message) => message.body);
+    },
+});
+
+export const purge = internalMutation({
+    args: {},
+    handler: async (ctx) => {
+        for (const message of await ctx.db.query("messages").collect()) {
+            await ctx.db.delete(message._id);
+        }
+    },
+});
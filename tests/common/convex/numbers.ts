@@ -0,0 +1,15 @@
+import { paginationOptsValidator, paginationResultValidator } from "convex/server";
+import { v } from "convex/values";
+import { query } from "./_generated/server";
+
+// Pages through the numbers 0..10, using the next number as the cursor
+export const page = query({
+    args: { paginationOpts: paginationOptsValidator },
+    returns: paginationResultValidator(v.number()),
+    handler: async (ctx, { paginationOpts }) => {
+        const start = paginationOpts.cursor === null ? 0 : Number(paginationOpts.cursor);
+        const end = Math.min(start + paginationOpts.numItems, 10);
+        const page = Array.from({ length: end - start }, (_, i) => start + i);
+        return { page, isDone: end === 10, continueCursor: String(end) };
+    },
+});
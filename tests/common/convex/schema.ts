@@ -0,0 +1,16 @@
+import { defineSchema, defineTable } from "convex/server";
+import { v } from "convex/values";
+
+export default defineSchema({
+    users: defineTable({
+        name: v.string(),
+    }),
+    messages: defineTable({
+        author: v.id("users"),
+        channel: v.string(),
+        body: v.string(),
+        priority: v.optional(v.number()),
+    })
+        .index("by_channel", ["channel"])
+        .index("by_channel_priority", ["channel", "priority"]),
+});
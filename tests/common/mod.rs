@@ -0,0 +1,36 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use std::collections::BTreeMap;
+
+use convex::Value as ConvexValue;
+
+/// The types generated from the Convex functions in `tests/common/convex`
+pub mod convex_types
+{
+    convex_typegen_macros::schema!("tests/common/convex", functions = "tests/common/convex/*.ts");
+}
+
+/// The directory holding the fixture schema and functions
+pub const CONVEX_DIR: &str = "tests/common/convex";
+
+/// Builds a Convex object from its fields
+pub fn object(fields: Vec<(&str, ConvexValue)>) -> ConvexValue
+{
+    ConvexValue::Object(
+        fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect::<BTreeMap<_, _>>(),
+    )
+}
+
+/// Arguments for `messages:send`
+pub fn send(author: &str, body: &str) -> convex_types::SendArgs
+{
+    convex_types::SendArgs {
+        author: author.to_string(),
+        body: body.to_string(),
+        r#type: None,
+    }
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use common::object;
+use convex::{FunctionResult, Value as ConvexValue};
+use convex_typegen::decode::{from_function_result, from_value};
+use convex_typegen::errors::ConvexDecodeError;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Game
+{
+    win_count: i32,
+    loss_count: f64,
+    seed: i64,
+    avatar: Vec<u8>,
+    owner: Option<Owner>,
+    status: Status,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Owner
+{
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Status
+{
+    #[serde(rename = "active")]
+    Active,
+    #[serde(rename = "archived")]
+    Archived,
+}
+
+fn game_value() -> ConvexValue
+{
+    object(vec![
+        ("_id", ConvexValue::String("abc123".to_string())),
+        ("win_count", ConvexValue::Float64(3.0)),
+        ("loss_count", ConvexValue::Float64(1.5)),
+        ("seed", ConvexValue::Int64(42)),
+        ("avatar", ConvexValue::Bytes(vec![1, 2, 3])),
+        ("owner", object(vec![("name", ConvexValue::String("jamal".to_string()))])),
+        ("status", ConvexValue::String("active".to_string())),
+    ])
+}
+
+#[test]
+fn test_decode_nested_object()
+{
+    let game: Game = from_value(game_value()).unwrap();
+
+    assert_eq!(
+        game,
+        Game {
+            win_count: 3,
+            loss_count: 1.5,
+            seed: 42,
+            avatar: vec![1, 2, 3],
+            owner: Some(Owner {
+                name: "jamal".to_string()
+            }),
+            status: Status::Active,
+        }
+    );
+}
+
+#[test]
+fn test_decode_null_as_none()
+{
+    let owner: Option<Owner> = from_value(ConvexValue::Null).unwrap();
+    assert_eq!(owner, None);
+}
+
+#[test]
+fn test_decode_error_has_path()
+{
+    let value = object(vec![("name", ConvexValue::Int64(7))]);
+    let wrapper = object(vec![("owner", value)]);
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Wrapper
+    {
+        owner: Owner,
+    }
+
+    match from_value::<Wrapper>(wrapper) {
+        Err(ConvexDecodeError::InvalidValue { path, .. }) => assert_eq!(path, vec!["owner", "name"]),
+        other => panic!("Expected InvalidValue error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_decode_fractional_float_into_integer_fails()
+{
+    assert!(from_value::<i32>(ConvexValue::Float64(1.5)).is_err());
+}
+
+#[test]
+fn test_decode_function_result()
+{
+    let game: Game = from_function_result(FunctionResult::Value(game_value())).unwrap();
+    assert_eq!(game.status, Status::Active);
+
+    match from_function_result::<Game>(FunctionResult::ErrorMessage("boom".to_string())) {
+        Err(ConvexDecodeError::FunctionFailed(message)) => assert_eq!(message, "boom"),
+        other => panic!("Expected FunctionFailed error, got {:?}", other),
+    }
+}
@@ -0,0 +1,189 @@
+mod common;
+
+use std::path::Path;
+
+use common::convex_types::{ListArgs, SendArgs};
+use common::send;
+use convex::Value as ConvexValue;
+use convex_typegen::convex::{ConvexClientExt, ConvexSchema};
+use convex_typegen::emulator::{Database, Document, Emulator, IndexRange};
+use convex_typegen::errors::{ConvexCallError, ConvexEmulatorError};
+use convex_typegen::parse_schema;
+use futures::executor::block_on;
+use futures::StreamExt;
+
+fn schema() -> ConvexSchema
+{
+    parse_schema(Path::new(common::CONVEX_DIR).join("schema.ts")).unwrap()
+}
+
+fn fields<const N: usize>(fields: [(&str, ConvexValue); N]) -> Document
+{
+    fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect()
+}
+
+fn message(author: &str, channel: &str, body: &str) -> Document
+{
+    fields([("author", author.into()), ("channel", channel.into()), ("body", body.into())])
+}
+
+fn bodies(documents: Vec<&Document>) -> Vec<String>
+{
+    documents
+        .into_iter()
+        .map(|document| match &document["body"] {
+            ConvexValue::String(body) => body.clone(),
+            other => panic!("Expected string body, got {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_database_writes_are_validated()
+{
+    let mut database = Database::new(&schema()).unwrap();
+
+    let ada = database.insert("users", fields([("name", "Ada".into())])).unwrap();
+    let id = database.insert("messages", message(&ada, "general", "hi")).unwrap();
+
+    let document = database.get(&id).unwrap();
+    assert_eq!(document["_id"], ConvexValue::String(id.clone()));
+    assert!(matches!(document["_creationTime"], ConvexValue::Float64(_)));
+
+    // Ids must point into the right table
+    let result = database.insert("messages", message(&id, "general", "hi"));
+    assert!(matches!(result, Err(ConvexEmulatorError::InvalidDocument { .. })));
+
+    // System fields can't be written
+    let result = database.patch(&id, fields([("_id", "other".into())]));
+    assert!(matches!(result, Err(ConvexEmulatorError::LimitViolation(_))));
+
+    let result = database.patch(&id, fields([("priority", "high".into())]));
+    assert!(matches!(result, Err(ConvexEmulatorError::InvalidDocument { .. })));
+
+    database.patch(&id, fields([("priority", 1.0.into())])).unwrap();
+    assert_eq!(database.get(&id).unwrap()["body"], ConvexValue::from("hi"));
+    assert_eq!(database.get(&id).unwrap()["priority"], ConvexValue::Float64(1.0));
+
+    database.replace(&id, message(&ada, "general", "edited")).unwrap();
+    assert!(!database.get(&id).unwrap().contains_key("priority"));
+
+    assert!(matches!(
+        database.insert("posts", Document::new()),
+        Err(ConvexEmulatorError::UnknownTable(_))
+    ));
+
+    database.delete(&id).unwrap();
+    assert!(database.get(&id).is_none());
+    assert!(matches!(database.delete(&id), Err(ConvexEmulatorError::DocumentNotFound(_))));
+}
+
+#[test]
+fn test_index_range_reads()
+{
+    let mut database = Database::new(&schema()).unwrap();
+    let ada = database.insert("users", fields([("name", "Ada".into())])).unwrap();
+
+    for (channel, body, priority) in [
+        ("general", "first", Some(2.0)),
+        ("random", "second", None),
+        ("general", "third", None),
+        ("general", "fourth", Some(1.0)),
+        ("general", "fifth", Some(2.0)),
+    ] {
+        let mut document = message(&ada, channel, body);
+        if let Some(priority) = priority {
+            document.insert("priority".to_string(), priority.into());
+        }
+        database.insert("messages", document).unwrap();
+    }
+
+    assert_eq!(
+        bodies(database.scan("messages").unwrap()),
+        ["first", "second", "third", "fourth", "fifth"]
+    );
+
+    let general = database
+        .query_index("messages", "by_channel", &IndexRange::new().eq("channel", "general"))
+        .unwrap();
+    assert_eq!(bodies(general), ["first", "third", "fourth", "fifth"]);
+
+    // Missing fields sort first, ties are broken by creation time
+    let by_priority = database
+        .query_index("messages", "by_channel_priority", &IndexRange::new().eq("channel", "general"))
+        .unwrap();
+    assert_eq!(bodies(by_priority), ["third", "fourth", "first", "fifth"]);
+
+    let range = IndexRange::new()
+        .eq("channel", "general")
+        .gt("priority", 1.0)
+        .lte("priority", 2.0);
+    let ranged = database.query_index("messages", "by_channel_priority", &range).unwrap();
+    assert_eq!(bodies(ranged), ["first", "fifth"]);
+
+    let out_of_order = IndexRange::new().eq("priority", 1.0);
+    assert!(matches!(
+        database.query_index("messages", "by_channel_priority", &out_of_order),
+        Err(ConvexEmulatorError::InvalidIndexRange { .. })
+    ));
+    assert!(matches!(
+        database.query_index("messages", "by_author", &IndexRange::new()),
+        Err(ConvexEmulatorError::UnknownIndex { .. })
+    ));
+}
+
+fn emulator() -> (Emulator, String)
+{
+    let mut emulator = Emulator::new(&schema()).unwrap();
+    let ada = emulator.database().insert("users", fields([("name", "Ada".into())])).unwrap();
+
+    emulator
+        .on_mutation(|database, args: SendArgs| {
+            let id = database.insert("messages", message(&args.author, "general", &args.body))?;
+            if args.body.is_empty() {
+                anyhow::bail!("Empty message");
+            }
+            Ok(id)
+        })
+        .on_query(|database, _: ListArgs| {
+            Ok(bodies(database.query_index(
+                "messages",
+                "by_channel",
+                &IndexRange::new().eq("channel", "general"),
+            )?))
+        });
+
+    (emulator, ada)
+}
+
+#[test]
+fn test_emulator_runs_functions()
+{
+    let (mut emulator, ada) = emulator();
+
+    block_on(emulator.call_mutation(send(&ada, "hello"))).unwrap();
+    assert_eq!(block_on(emulator.call_query(ListArgs {})).unwrap(), vec!["hello"]);
+
+    // A failed mutation leaves no trace
+    let result = block_on(emulator.call_mutation(send(&ada, "")));
+    assert!(matches!(result, Err(ConvexCallError::FunctionFailed(message)) if message == "Empty message"));
+    let result = block_on(emulator.call_mutation(send("nobody", "hi")));
+    assert!(matches!(result, Err(ConvexCallError::FunctionFailed(_))));
+    assert_eq!(emulator.database().scan("messages").unwrap().len(), 1);
+}
+
+#[test]
+fn test_emulator_subscriptions_are_reactive()
+{
+    let (mut emulator, ada) = emulator();
+
+    let mut subscription = block_on(emulator.subscribe_query(ListArgs {})).unwrap();
+    assert!(block_on(subscription.next()).unwrap().unwrap().is_empty());
+
+    block_on(emulator.call_mutation(send(&ada, "one"))).unwrap();
+    block_on(emulator.call_mutation(send(&ada, ""))).unwrap_err();
+    block_on(emulator.call_mutation(send(&ada, "two"))).unwrap();
+
+    assert_eq!(block_on(subscription.next()).unwrap().unwrap(), vec!["one"]);
+    assert_eq!(block_on(subscription.next()).unwrap().unwrap(), vec!["one", "two"]);
+}
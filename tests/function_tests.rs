@@ -49,6 +49,7 @@ export const testQuery = query({
         schema_path,
         function_paths: vec![function_path],
         out_file: temp_dir.path().join("types.rs").to_string_lossy().to_string(),
+        ..Default::default()
     };
 
     let result = generate(config);
@@ -81,6 +82,7 @@ fn test_invalid_function_args()
         schema_path,
         function_paths: vec![function_path],
         out_file: temp_dir.path().join("types.rs").to_string_lossy().to_string(),
+        ..Default::default()
     };
 
     match generate(config) {
@@ -88,3 +90,136 @@ fn test_invalid_function_args()
         other => panic!("Expected InvalidSchema error, got {:?}", other),
     }
 }
+
+#[test]
+fn test_function_returns_validator()
+{
+    let temp_dir = setup_test_dir();
+
+    let function_path = temp_dir.path().join("posts.ts");
+    fs::write(
+        &function_path,
+        r#"
+import { query } from "./_generated/server";
+import { v } from "convex/values";
+
+export const count = query({
+    args: {},
+    returns: v.int64(),
+    handler: async (ctx, args) => 0n,
+});
+
+export const list = query({
+    args: {},
+    handler: async (ctx, args) => [],
+});
+    "#,
+    )
+    .unwrap();
+
+    let functions = convex_typegen::parse_functions(vec![function_path]).unwrap();
+    let count = functions.iter().find(|f| f.name == "count").unwrap();
+    let list = functions.iter().find(|f| f.name == "list").unwrap();
+
+    assert_eq!(count.returns.as_ref().unwrap()["type"], "int64");
+    assert!(list.returns.is_none());
+}
+
+#[test]
+fn test_function_thrown_errors()
+{
+    let temp_dir = setup_test_dir();
+
+    let function_path = temp_dir.path().join("orders.ts");
+    fs::write(
+        &function_path,
+        r#"
+import { mutation } from "./_generated/server";
+import { ConvexError, v } from "convex/values";
+
+function load(id) {
+    throw new ConvexError({ code: "NOT_FOUND", id });
+}
+
+const unrelated = () => {
+    throw new ConvexError("Never called");
+};
+
+export const cancel = mutation({
+    args: { id: v.id("orders") },
+    handler: async (ctx, args) => {
+        load(args.id);
+        load(args.id);
+        throw new ConvexError({ code: "LOCKED", until: Date.now(), retries: 3 });
+    },
+});
+    "#,
+    )
+    .unwrap();
+
+    let functions = convex_typegen::parse_functions(vec![function_path]).unwrap();
+    let errors = &functions[0].errors;
+
+    // Errors thrown by helpers are found once, errors of functions never called are not. The
+    // helper's `id` is its own parameter, not the handler's argument.
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0]["properties"]["code"]["value"]["value"], "NOT_FOUND");
+    assert_eq!(errors[0]["properties"]["id"]["type"], "any");
+    assert_eq!(errors[1]["properties"]["code"]["value"]["value"], "LOCKED");
+    assert_eq!(errors[1]["properties"]["until"]["type"], "any");
+    assert_eq!(errors[1]["properties"]["retries"]["type"], "number");
+}
+
+#[test]
+fn test_thrown_error_types_respect_shadowing()
+{
+    let temp_dir = setup_test_dir();
+
+    let function_path = temp_dir.path().join("orders.ts");
+    fs::write(
+        &function_path,
+        r#"
+import { mutation } from "./_generated/server";
+import { ConvexError, v } from "convex/values";
+
+export const cancel = mutation({
+    args: { id: v.id("orders"), reason: v.string() },
+    handler: async (ctx, { id, reason: why }) => {
+        throw new ConvexError({ code: "A", id, why, reason: reason });
+        for (const id of [1, 2]) {
+            throw new ConvexError({ code: "B", id, why });
+        }
+    },
+});
+
+export const refund = mutation({
+    args: { id: v.id("orders") },
+    handler: async (ctx, args) => {
+        const other = { id: 1 };
+        throw new ConvexError({ code: "C", id: args.id, other: other.id });
+        const inner = (args) => {
+            throw new ConvexError({ code: "D", id: args.id });
+        };
+    },
+});
+    "#,
+    )
+    .unwrap();
+
+    let functions = convex_typegen::parse_functions(vec![function_path]).unwrap();
+    let cancel = &functions.iter().find(|f| f.name == "cancel").unwrap().errors;
+    let refund = &functions.iter().find(|f| f.name == "refund").unwrap().errors;
+
+    // Destructured arguments keep their type under their local name only
+    assert_eq!(cancel[0]["properties"]["id"]["type"], "id");
+    assert_eq!(cancel[0]["properties"]["why"]["type"], "string");
+    assert_eq!(cancel[0]["properties"]["reason"]["type"], "any");
+    // A loop variable shadows the argument of the same name
+    assert_eq!(cancel[1]["properties"]["id"]["type"], "any");
+    assert_eq!(cancel[1]["properties"]["why"]["type"], "string");
+
+    // Only members of the args object are arguments, also when nested functions rebind `args`
+    assert_eq!(refund[0]["properties"]["id"]["type"], "id");
+    assert_eq!(refund[0]["properties"]["other"]["type"], "any");
+    assert_eq!(refund[1]["properties"]["id"]["type"], "any");
+}
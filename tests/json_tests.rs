@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+
+use convex::Value as ConvexValue;
+use convex_typegen::decode::from_value;
+use convex_typegen::json::{from_convex_json, to_args_map, to_convex_json};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Upload
+{
+    #[serde(with = "convex_typegen::json::int64")]
+    size: i64,
+    #[serde(with = "convex_typegen::json::bytes")]
+    data: Vec<u8>,
+    #[serde(default, with = "convex_typegen::json::int64::option")]
+    parent: Option<i64>,
+}
+
+#[test]
+fn test_int64_and_bytes_encoding()
+{
+    assert_eq!(to_convex_json(ConvexValue::Int64(1)), json!({ "$integer": "AQAAAAAAAAA=" }));
+    assert_eq!(to_convex_json(ConvexValue::Bytes(vec![1, 2, 3])), json!({ "$bytes": "AQID" }));
+    assert_eq!(to_convex_json(ConvexValue::Float64(1.5)), json!(1.5));
+}
+
+#[test]
+fn test_special_floats_roundtrip()
+{
+    for f in [f64::INFINITY, f64::NEG_INFINITY, -0.0] {
+        let encoded = to_convex_json(ConvexValue::Float64(f));
+        assert!(encoded.get("$float").is_some(), "{} should be wrapped, got {}", f, encoded);
+
+        match from_convex_json(encoded).unwrap() {
+            ConvexValue::Float64(decoded) => assert_eq!(decoded.to_bits(), f.to_bits()),
+            other => panic!("Expected Float64, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_nested_roundtrip()
+{
+    let value = ConvexValue::Object(BTreeMap::from([
+        ("count".to_string(), ConvexValue::Int64(-42)),
+        (
+            "items".to_string(),
+            ConvexValue::Array(vec![ConvexValue::Bytes(vec![0xff]), ConvexValue::Null]),
+        ),
+        ("name".to_string(), ConvexValue::String("convex".to_string())),
+    ]));
+
+    assert_eq!(from_convex_json(to_convex_json(value.clone())).unwrap(), value);
+}
+
+#[test]
+fn test_plain_numbers_are_floats()
+{
+    assert_eq!(from_convex_json(json!(3)).unwrap(), ConvexValue::Float64(3.0));
+}
+
+#[test]
+fn test_invalid_wrapper_reports_path()
+{
+    let err = from_convex_json(json!({ "user": { "age": { "$integer": "AQ==" } } })).unwrap_err();
+    assert_eq!(err.path, vec!["user", "age"]);
+
+    let err = from_convex_json(json!({ "$unknown": 1, "other": 2 })).unwrap_err();
+    assert!(err.path.is_empty());
+}
+
+#[test]
+fn test_with_modules_roundtrip()
+{
+    let upload = Upload {
+        size: 1,
+        data: vec![1, 2, 3],
+        parent: None,
+    };
+
+    let encoded = serde_json::to_value(&upload).unwrap();
+    assert_eq!(
+        encoded,
+        json!({ "size": { "$integer": "AQAAAAAAAAA=" }, "data": { "$bytes": "AQID" }, "parent": null })
+    );
+    assert_eq!(serde_json::from_value::<Upload>(encoded).unwrap(), upload);
+
+    // The same fields decode straight from a Convex value
+    let value = ConvexValue::Object(BTreeMap::from([
+        ("size".to_string(), ConvexValue::Int64(1)),
+        ("data".to_string(), ConvexValue::Bytes(vec![1, 2, 3])),
+        ("parent".to_string(), ConvexValue::Int64(7)),
+    ]));
+    let decoded: Upload = from_value(value).unwrap();
+    assert_eq!(decoded.parent, Some(7));
+}
+
+#[test]
+fn test_with_modules_reject_extra_keys()
+{
+    let extra = json!({ "size": { "$integer": "AQAAAAAAAAA=", "junk": 1 }, "data": { "$bytes": "AQID" } });
+    let err = serde_json::from_value::<Upload>(extra).unwrap_err();
+    assert!(err.to_string().contains("invalid length 2"), "unexpected error: {}", err);
+
+    let extra = json!({ "size": { "$integer": "AQAAAAAAAAA=" }, "data": { "$bytes": "AQID", "$integer": "AQ==" } });
+    assert!(serde_json::from_value::<Upload>(extra).is_err());
+
+    let unknown = json!({ "size": { "$float": "AAAAAAAA8D8=" }, "data": { "$bytes": "AQID" } });
+    let err = serde_json::from_value::<Upload>(unknown).unwrap_err();
+    assert!(
+        err.to_string().contains("unknown field `$float`"),
+        "unexpected error: {}",
+        err
+    );
+
+    let empty = json!({ "size": 1, "data": {} });
+    assert!(serde_json::from_value::<Upload>(empty).is_err());
+}
+
+#[test]
+fn test_prepare_args_decodes_wrappers()
+{
+    use convex::ConvexClient;
+    use convex_typegen::convex::ConvexClientExt;
+
+    let args = match serde_json::to_value(Upload {
+        size: 5,
+        data: vec![9],
+        parent: Some(2),
+    })
+    .unwrap()
+    {
+        serde_json::Value::Object(map) => map.into_iter().collect::<BTreeMap<_, _>>(),
+        other => panic!("Expected object, got {}", other),
+    };
+
+    let prepared = ConvexClient::prepare_args(args.clone());
+    assert_eq!(prepared, ConvexClient::try_prepare_args(args).unwrap());
+    assert_eq!(prepared["size"], ConvexValue::Int64(5));
+    assert_eq!(prepared["data"], ConvexValue::Bytes(vec![9]));
+    assert_eq!(prepared["parent"], ConvexValue::Int64(2));
+}
+
+#[test]
+fn test_try_prepare_args_rejects_malformed_wrappers()
+{
+    use convex::ConvexClient;
+    use convex_typegen::convex::ConvexClientExt;
+
+    let args = BTreeMap::from([("upload".to_string(), json!({ "parent": { "$integer": "AQID" } }))]);
+
+    let error = ConvexClient::try_prepare_args(args).unwrap_err();
+    assert_eq!(error.path, vec!["upload", "parent"]);
+    assert!(error.details.contains("8 bytes"), "unexpected details: {}", error.details);
+}
+
+#[test]
+fn test_args_that_dont_serialize_to_an_object_are_rejected()
+{
+    let error = to_args_map("not an object").unwrap_err();
+    assert!(
+        error.details.contains("must serialize to an object"),
+        "unexpected details: {}",
+        error.details
+    );
+
+    // JSON object keys must be strings
+    let args = BTreeMap::from([(vec![1u8], "value")]);
+    assert!(to_args_map(args).is_err());
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::object;
+use convex::Value as ConvexValue;
+use convex_typegen::errors::LimitViolationKind;
+use convex_typegen::limits::{check_document, check_serialized_document, check_value, MAX_ARRAY_LENGTH, MAX_VALUE_SIZE};
+use serde::Serialize;
+
+#[test]
+fn test_valid_document_passes()
+{
+    let doc = object(vec![
+        ("title", ConvexValue::String("Hello".to_string())),
+        ("tags", ConvexValue::Array(vec![ConvexValue::String("a".to_string())])),
+    ]);
+
+    assert!(check_document(&doc).is_ok());
+}
+
+#[test]
+fn test_reports_every_violation_with_path()
+{
+    let doc = object(vec![
+        ("_secret", ConvexValue::Null),
+        (
+            "nested",
+            object(vec![
+                ("$bad", ConvexValue::Null),
+                ("", ConvexValue::Null),
+                ("héllo", ConvexValue::Null),
+            ]),
+        ),
+        ("items", ConvexValue::Array(vec![ConvexValue::Null; MAX_ARRAY_LENGTH + 1])),
+    ]);
+
+    let violations = check_document(&doc).unwrap_err();
+    let found: Vec<(String, &LimitViolationKind)> = violations.iter().map(|v| (v.path.join("."), &v.kind)).collect();
+
+    assert_eq!(violations.len(), 5);
+    assert!(found.contains(&(
+        "_secret".to_string(),
+        &LimitViolationKind::ReservedFieldName("_secret".to_string())
+    )));
+    assert!(found.contains(&(
+        "nested.$bad".to_string(),
+        &LimitViolationKind::ReservedFieldName("$bad".to_string())
+    )));
+    assert!(found.contains(&("nested.".to_string(), &LimitViolationKind::EmptyFieldName)));
+    assert!(found.contains(&(
+        "nested.héllo".to_string(),
+        &LimitViolationKind::InvalidFieldName("héllo".to_string())
+    )));
+    assert!(found.contains(&(
+        "items".to_string(),
+        &LimitViolationKind::ArrayTooLong {
+            length: MAX_ARRAY_LENGTH + 1,
+            limit: MAX_ARRAY_LENGTH
+        }
+    )));
+}
+
+#[test]
+fn test_size_and_depth_limits()
+{
+    let large = ConvexValue::String("x".repeat(MAX_VALUE_SIZE));
+    let violations = check_value(&large).unwrap_err();
+    assert!(matches!(violations[0].kind, LimitViolationKind::ValueTooLarge { .. }));
+
+    let mut deep = ConvexValue::Null;
+    for _ in 0..20 {
+        deep = ConvexValue::Array(vec![deep]);
+    }
+    let violations = check_value(&deep).unwrap_err();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].path.len(), 16);
+    assert!(matches!(
+        violations[0].kind,
+        LimitViolationKind::NestingTooDeep { depth: 17, limit: 16 }
+    ));
+
+    // Underscore-prefixed names are only reserved at the top level of documents
+    assert!(check_value(&object(vec![("_id", ConvexValue::Null)])).is_ok());
+}
+
+#[test]
+fn test_serialized_document()
+{
+    #[derive(Serialize)]
+    struct Post
+    {
+        title: String,
+        meta: BTreeMap<String, String>,
+    }
+
+    let post = Post {
+        title: "Hello".to_string(),
+        meta: BTreeMap::from([("naïve".to_string(), "x".to_string())]),
+    };
+
+    let violations = check_serialized_document(&post).unwrap_err();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].path, vec!["meta", "naïve"]);
+}
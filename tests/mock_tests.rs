@@ -0,0 +1,200 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::convex_types::{mock_transport, ConvexCall, ListArgs, PurgeArgs, RecountArgs, SendArgs, SendError};
+use convex::{ConvexError, FunctionResult, Value as ConvexValue};
+use convex_typegen::client::ConvexTransport;
+use convex_typegen::convex::ConvexClientExt;
+use convex_typegen::errors::{ArgumentErrorKind, ConvexCallError, TypedCallError};
+use convex_typegen::mock::MockTransport;
+use convex_typegen::registry::FunctionType;
+use futures::executor::block_on;
+use futures::StreamExt;
+
+fn send(body: &str) -> SendArgs
+{
+    common::send("ada", body)
+}
+
+fn bodies(calls: Vec<SendArgs>) -> Vec<String>
+{
+    calls.into_iter().map(|args| args.body).collect()
+}
+
+#[test]
+fn test_mock_handlers_and_recorded_calls()
+{
+    let mut mock = MockTransport::new();
+    mock.on(|args: SendArgs| format!("id-{}", args.body));
+    mock.respond::<ListArgs>(vec!["hello".to_string()]);
+
+    assert_eq!(block_on(mock.call_mutation(send("a"))).unwrap(), "id-a");
+    assert_eq!(block_on(mock.call_mutation(send("b"))).unwrap(), "id-b");
+    assert_eq!(block_on(mock.call_query(ListArgs {})).unwrap(), vec!["hello"]);
+
+    let mut subscription = block_on(mock.subscribe_query(ListArgs {})).unwrap();
+    assert_eq!(block_on(subscription.next()).unwrap().unwrap(), vec!["hello"]);
+
+    assert_eq!(mock.calls().len(), 4);
+    assert_eq!(mock.calls()[0].function_type, FunctionType::Mutation);
+    assert_eq!(bodies(mock.calls_to::<SendArgs>()), vec!["a", "b"]);
+
+    mock.clear_calls();
+    assert!(mock.calls().is_empty());
+}
+
+#[test]
+fn test_call_function_uses_the_endpoint_of_each_kind()
+{
+    let mut mock = mock_transport();
+    mock.respond::<ListArgs>(Vec::new());
+    mock.respond::<SendArgs>("message_1".to_string());
+    mock.respond::<RecountArgs>(0);
+
+    block_on(mock.call_function(ListArgs {})).unwrap();
+    block_on(mock.call_function(send("a"))).unwrap();
+    block_on(mock.call_function(RecountArgs {})).unwrap();
+
+    let kinds = mock.calls().iter().map(|call| call.function_type).collect::<Vec<_>>();
+    assert_eq!(kinds, vec![FunctionType::Query, FunctionType::Mutation, FunctionType::Action]);
+}
+
+#[test]
+fn test_dispatch_call_through_mock()
+{
+    let mut mock = mock_transport();
+    mock.on(|args: SendArgs| format!("id-{}", args.body));
+    mock.respond::<RecountArgs>(3);
+
+    let calls = [ConvexCall::from(send("a")), ConvexCall::from(RecountArgs {})];
+    assert_eq!(calls[0].path(), "messages:send");
+
+    let results = calls.map(|call| block_on(call.dispatch(&mut mock)).unwrap());
+    assert_eq!(results[0], ConvexValue::String("id-a".to_string()));
+    assert_eq!(results[1], ConvexValue::Float64(3.0));
+
+    let kinds = mock.calls().iter().map(|call| call.function_type).collect::<Vec<_>>();
+    assert_eq!(kinds, vec![FunctionType::Mutation, FunctionType::Action]);
+}
+
+#[test]
+fn test_mock_errors()
+{
+    let mut mock = MockTransport::new();
+
+    // Nothing registered behaves like an unreachable deployment
+    assert!(matches!(
+        block_on(mock.call_query(ListArgs {})),
+        Err(ConvexCallError::Client(_))
+    ));
+
+    mock.respond_with::<SendArgs>(FunctionResult::ErrorMessage("Too long".to_string()));
+    let result = block_on(mock.call_mutation(send("a")));
+    assert!(matches!(result, Err(ConvexCallError::FunctionFailed(message)) if message == "Too long"));
+}
+
+#[test]
+fn test_mock_for_functions_checks_registry()
+{
+    let mut mock = mock_transport();
+    mock.on(|args: SendArgs| args.body);
+    mock.respond::<PurgeArgs>(serde_json::Value::Null);
+
+    assert_eq!(block_on(mock.call_mutation(send("a"))).unwrap(), "a");
+
+    // Internal functions can't be called by clients, and functions must be called with their kind
+    assert!(matches!(
+        block_on(mock.call_mutation(PurgeArgs {})),
+        Err(ConvexCallError::Client(_))
+    ));
+    assert!(block_on(mock.query("messages:send", BTreeMap::new())).is_err());
+    assert!(block_on(mock.query("messages:edit", BTreeMap::new())).is_err());
+    assert_eq!(mock.calls().len(), 1);
+}
+
+fn thrown(data: ConvexValue) -> FunctionResult
+{
+    FunctionResult::ConvexError(ConvexError {
+        message: "Uncaught ConvexError".to_string(),
+        data,
+    })
+}
+
+#[test]
+fn test_try_call_decodes_thrown_data()
+{
+    let mut mock = MockTransport::new();
+
+    mock.respond::<SendArgs>("id".to_string());
+    assert_eq!(block_on(mock.try_call(send("a"))).unwrap(), "id");
+
+    let too_long = ConvexValue::Object(
+        [("code".to_string(), "TOO_LONG".into()), ("limit".to_string(), 280.0.into())]
+            .into_iter()
+            .collect(),
+    );
+    mock.respond_with::<SendArgs>(thrown(too_long));
+    let result = block_on(mock.try_call(send("a")));
+    assert!(matches!(result, Err(TypedCallError::Thrown { data: SendError::TooLong { limit }, .. }) if limit == 280.0));
+
+    mock.respond_with::<SendArgs>(thrown("Slow down".into()));
+    let result = block_on(mock.try_call(send("a")));
+    assert!(
+        matches!(result, Err(TypedCallError::Thrown { data: SendError::Message(message), .. }) if message == "Slow down")
+    );
+
+    mock.respond_with::<SendArgs>(thrown(ConvexValue::Int64(7)));
+    let result = block_on(mock.try_call(send("a")));
+    assert!(matches!(
+        result,
+        Err(TypedCallError::Thrown {
+            data: SendError::Other(_),
+            ..
+        })
+    ));
+
+    mock.respond_with::<SendArgs>(FunctionResult::ErrorMessage("Server error".to_string()));
+    let result = block_on(mock.try_call(send("a")));
+    assert!(matches!(
+        result,
+        Err(TypedCallError::Call(ConvexCallError::FunctionFailed(_)))
+    ));
+}
+
+#[test]
+fn test_rejected_arguments_point_at_rust_fields()
+{
+    let mut mock = MockTransport::new();
+
+    mock.respond_with::<SendArgs>(FunctionResult::ErrorMessage(
+        "[CONVEX M(messages:send)] [Request ID: 2f3a] Server Error\nArgumentValidationError: Value does not match \
+         validator.\nPath: .type.labels[0]\nValue: 1.0\nValidator: v.string()"
+            .to_string(),
+    ));
+    let error = match block_on(mock.call_mutation(send("a"))) {
+        Err(ConvexCallError::InvalidArguments(error)) => error,
+        other => panic!("Expected InvalidArguments, got {:?}", other),
+    };
+    assert_eq!(error.function_path, "messages:send");
+    assert_eq!(error.path, vec!["type", "labels", "0"]);
+    assert_eq!(error.rust_path.as_deref(), Some("SendArgs.r#type.labels[0]"));
+    assert_eq!(
+        error.to_string(),
+        "Invalid arguments for 'messages:send': 'SendArgs.r#type.labels[0]' does not match v.string(), received 1.0"
+    );
+
+    mock.respond_with::<SendArgs>(FunctionResult::ErrorMessage(
+        "ArgumentValidationError: Object is missing the required field `body`. Consider wrapping the field validator in \
+         `v.optional(...)` if this is expected.\n\nObject: {}\nValidator: v.object({body: v.string()})"
+            .to_string(),
+    ));
+    let result = block_on(mock.try_call(send("a")));
+    assert!(matches!(
+        result,
+        Err(TypedCallError::Call(ConvexCallError::InvalidArguments(error)))
+            if error.kind == ArgumentErrorKind::MissingField
+                && error.function_path == "messages:send"
+                && error.rust_path.as_deref() == Some("SendArgs.body")
+    ));
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "outbox")]
+
+mod common;
+
+use std::fs;
+
+use common::convex_types::{ConvexCall, RecountArgs, SendArgs};
+use convex::{ConvexError, FunctionResult, Value as ConvexValue};
+use convex_typegen::errors::{ConvexCallError, ConvexOutboxError};
+use convex_typegen::mock::MockTransport;
+use convex_typegen::outbox::Outbox;
+use futures::executor::block_on;
+use tempdir::TempDir;
+
+fn send(body: &str) -> SendArgs
+{
+    common::send("ada", body)
+}
+
+#[test]
+fn test_outbox_survives_reopen()
+{
+    let temp_dir = TempDir::new("convex_outbox_test").expect("Failed to create temp directory");
+    let path = temp_dir.path().join("outbox.jsonl");
+
+    let mut outbox = Outbox::<ConvexCall>::open(&path).unwrap();
+    assert_eq!(outbox.push(send("first")).unwrap(), 1);
+    assert_eq!(outbox.push(RecountArgs {}).unwrap(), 2);
+
+    let reopened = Outbox::<ConvexCall>::open(&path).unwrap();
+    let pending = reopened.pending().collect::<Vec<_>>();
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].id, 1);
+    assert!(matches!(&pending[0].call, ConvexCall::Send(args) if args.body == "first"));
+    assert_eq!(pending[1].call.path(), "counter:recount");
+}
+
+#[test]
+fn test_replay_in_order_with_failures_and_interruption()
+{
+    let temp_dir = TempDir::new("convex_outbox_test").expect("Failed to create temp directory");
+    let path = temp_dir.path().join("outbox.jsonl");
+
+    let mut outbox = Outbox::<ConvexCall>::open(&path).unwrap();
+    outbox.push(send("ok")).unwrap();
+    outbox.push(RecountArgs {}).unwrap();
+    for message in ["rejected", "offline", "later"] {
+        outbox.push(send(message)).unwrap();
+    }
+
+    let mut transport = MockTransport::new();
+    transport.on_result(|args: SendArgs| match args.body.as_str() {
+        "rejected" => Ok(FunctionResult::ConvexError(ConvexError {
+            message: "Uncaught ConvexError: Message is empty".to_string(),
+            data: "Message is empty".into(),
+        })),
+        "offline" => Err(anyhow::anyhow!("connection lost")),
+        body => Ok(FunctionResult::Value(format!("id-{}", body).into())),
+    });
+    transport.respond::<RecountArgs>(2);
+
+    let report = block_on(outbox.replay(&mut transport)).unwrap();
+    assert_eq!(report.delivered.len(), 2);
+    assert_eq!(report.delivered[0].1, ConvexValue::String("id-ok".to_string()));
+    assert_eq!(report.delivered[1].1, ConvexValue::Float64(2.0));
+    assert_eq!(report.failed.len(), 1);
+    assert!(matches!(
+        &report.failed[0].error,
+        ConvexCallError::ApplicationError(error) if error.data == "Message is empty".into()
+    ));
+    assert!(matches!(report.interrupted, Some(ConvexCallError::Client(_))));
+
+    // Calls to different functions are sent in the order they were pushed
+    let paths = transport.calls().iter().map(|call| call.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec!["messages:send", "counter:recount", "messages:send", "messages:send"]
+    );
+    let sent = transport
+        .calls_to::<SendArgs>()
+        .into_iter()
+        .map(|args| args.body)
+        .collect::<Vec<_>>();
+    assert_eq!(sent, vec!["ok", "rejected", "offline"]);
+
+    // The interrupted entry and everything after it are still pending, also after a restart
+    let mut outbox = Outbox::<ConvexCall>::open(&path).unwrap();
+    assert_eq!(outbox.len(), 2);
+
+    let mut transport = MockTransport::new();
+    transport.respond::<SendArgs>("id".to_string());
+    let report = block_on(outbox.replay(&mut transport)).unwrap();
+    assert_eq!(report.delivered.len(), 2);
+    assert!(outbox.is_empty());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "");
+}
+
+#[test]
+fn test_torn_last_line_is_dropped()
+{
+    let temp_dir = TempDir::new("convex_outbox_test").expect("Failed to create temp directory");
+    let path = temp_dir.path().join("outbox.jsonl");
+
+    let mut outbox = Outbox::<ConvexCall>::open(&path).unwrap();
+    outbox.push(send("kept")).unwrap();
+
+    let mut contents = fs::read_to_string(&path).unwrap();
+    contents.push_str(r#"{"op":"push","id":2,"pa"#);
+    fs::write(&path, contents).unwrap();
+
+    let mut outbox = Outbox::<ConvexCall>::open(&path).unwrap();
+    assert_eq!(outbox.len(), 1);
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+    assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    assert_eq!(outbox.push(send("next")).unwrap(), 2);
+    assert_eq!(Outbox::<ConvexCall>::open(&path).unwrap().len(), 2);
+}
+
+#[test]
+fn test_entries_of_unknown_functions_are_rejected()
+{
+    let temp_dir = TempDir::new("convex_outbox_test").expect("Failed to create temp directory");
+    let path = temp_dir.path().join("outbox.jsonl");
+
+    // Internal functions have no `ConvexCall` variant. The record is complete, so it is not
+    // mistaken for a torn last line.
+    fs::write(
+        &path,
+        "{\"op\":\"push\",\"id\":1,\"call\":{\"path\":\"messages:purge\",\"args\":{}}}\n",
+    )
+    .unwrap();
+
+    let result = Outbox::<ConvexCall>::open(&path);
+    assert!(matches!(result, Err(ConvexOutboxError::Corrupt { line: 1, .. })));
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::convex_types::PageArgs;
+use convex::Value as ConvexValue;
+use convex_typegen::convex::ConvexClientExt;
+use convex_typegen::errors::ConvexCallError;
+use convex_typegen::mock::MockTransport;
+use convex_typegen::pagination::{PageStatus, PaginationOpts, PaginationResult};
+use futures::executor::block_on;
+use futures::StreamExt;
+use serde_json::json;
+
+/// Serves the numbers 0..10, using the next number as the cursor
+fn numbers(args: PageArgs) -> PaginationResult<f64>
+{
+    let start: u32 = args.paginationOpts.cursor.map_or(0, |cursor| cursor.parse().unwrap());
+    let end = (start + args.paginationOpts.num_items as u32).min(10);
+    PaginationResult {
+        page: (start..end).map(f64::from).collect(),
+        is_done: end == 10,
+        continue_cursor: end.to_string(),
+        split_cursor: None,
+        page_status: None,
+    }
+}
+
+#[test]
+fn test_pagination_serde_shape()
+{
+    let opts = serde_json::to_value(PaginationOpts::new(25)).unwrap();
+    assert_eq!(opts, json!({ "numItems": 25.0, "cursor": null }));
+
+    let result: PaginationResult<String> = serde_json::from_value(json!({
+        "page": ["a"],
+        "isDone": false,
+        "continueCursor": "abc",
+        "splitCursor": null,
+        "pageStatus": "SplitRecommended",
+    }))
+    .unwrap();
+    assert_eq!(result.continue_cursor, "abc");
+    assert_eq!(result.split_cursor, None);
+    assert_eq!(result.page_status, Some(PageStatus::SplitRecommended));
+}
+
+#[test]
+fn test_paginate_follows_cursors()
+{
+    let mut mock = MockTransport::new();
+    mock.on(numbers);
+
+    let args = PageArgs {
+        paginationOpts: PaginationOpts::new(4),
+    };
+    let pages = block_on(mock.paginate(args).map(Result::unwrap).collect::<Vec<_>>());
+    assert_eq!(
+        pages,
+        vec![vec![0.0, 1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0, 7.0], vec![8.0, 9.0]]
+    );
+
+    let cursors = mock
+        .calls_to::<PageArgs>()
+        .into_iter()
+        .map(|args| args.paginationOpts.cursor)
+        .collect::<Vec<_>>();
+    assert_eq!(cursors, vec![None, Some("4".to_string()), Some("8".to_string())]);
+}
+
+#[test]
+fn test_prepared_pagination_opts_are_floats()
+{
+    let mut mock = MockTransport::new();
+    mock.on(numbers);
+
+    let mut args = PageArgs {
+        paginationOpts: PaginationOpts::new(4),
+    };
+    args.paginationOpts.maximum_rows_read = Some(100.0);
+    block_on(mock.call_query(args)).unwrap();
+
+    let Some(ConvexValue::Object(opts)) = mock.calls()[0].args.get("paginationOpts") else {
+        panic!("paginationOpts is not an object");
+    };
+    assert_eq!(opts.get("numItems"), Some(&ConvexValue::Float64(4.0)));
+    assert_eq!(opts.get("maximumRowsRead"), Some(&ConvexValue::Float64(100.0)));
+}
+
+#[test]
+fn test_paginate_stops_after_error()
+{
+    let mut mock = MockTransport::new();
+    mock.respond_with::<PageArgs>(convex::FunctionResult::ErrorMessage("Too many reads".to_string()));
+
+    let args = PageArgs {
+        paginationOpts: PaginationOpts::new(4),
+    };
+    let pages = block_on(mock.paginate(args).collect::<Vec<_>>());
+    assert_eq!(pages.len(), 1);
+    assert!(matches!(&pages[0], Err(ConvexCallError::FunctionFailed(message)) if message == "Too many reads"));
+}
@@ -2,7 +2,7 @@ use std::fs;
 use std::path::PathBuf;
 
 use convex_typegen::errors::ConvexTypeGeneratorError;
-use convex_typegen::{generate, Configuration};
+use convex_typegen::{generate, parse_schema, Configuration};
 use tempdir::TempDir;
 
 fn setup_test_schema(content: &str) -> (TempDir, PathBuf)
@@ -28,16 +28,123 @@ fn test_valid_schema()
         });
     "#;
 
-    let (_temp_dir, schema_path) = setup_test_schema(schema_content);
+    let (temp_dir, schema_path) = setup_test_schema(schema_content);
     let config = Configuration {
         schema_path,
-        out_file: "test_output.rs".to_string(),
+        out_file: temp_dir.path().join("test_output.rs").to_string_lossy().to_string(),
         ..Default::default()
     };
 
     assert!(generate(config).is_ok());
 }
 
+#[test]
+fn test_schema_indexes()
+{
+    let schema_content = r#"
+        import { defineSchema, defineTable } from "convex/server";
+        import { v } from "convex/values";
+
+        export default defineSchema({
+            messages: defineTable({
+                author: v.string(),
+                channel: v.string(),
+                body: v.string(),
+            })
+                .index("by_author", ["author"])
+                .searchIndex("search_body", { searchField: "body" })
+                .index("by_channel_author", ["channel", "author"]),
+            users: defineTable({
+                name: v.string(),
+            }),
+        });
+    "#;
+
+    let (_temp_dir, schema_path) = setup_test_schema(schema_content);
+    let schema = parse_schema(schema_path).unwrap();
+
+    let messages = &schema.tables[0];
+    assert_eq!(messages.columns.len(), 3);
+    let indexes = messages
+        .indexes
+        .iter()
+        .map(|index| (index.name.as_str(), index.fields.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        indexes,
+        vec![
+            ("by_author", vec!["author".to_string()]),
+            ("by_channel_author", vec!["channel".to_string(), "author".to_string()]),
+        ]
+    );
+    assert!(schema.tables[1].indexes.is_empty());
+}
+
+#[test]
+fn test_watched_paths()
+{
+    let config = Configuration {
+        schema_path: PathBuf::from("convex/schema.ts"),
+        function_paths: vec![
+            PathBuf::from("convex/games.ts"),
+            PathBuf::from("convex/admin/users.ts"),
+            PathBuf::from("shared.ts"),
+        ],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        config.watched_paths(),
+        vec![
+            PathBuf::from("convex/schema.ts"),
+            PathBuf::from("convex/games.ts"),
+            PathBuf::from("convex/admin/users.ts"),
+            PathBuf::from("shared.ts"),
+            PathBuf::from("convex"),
+            PathBuf::from("convex/admin"),
+        ]
+    );
+}
+
+#[test]
+fn test_watched_paths_include_imports()
+{
+    let temp_dir = TempDir::new("convex_watched_paths_test").expect("Failed to create temp directory");
+    let convex_dir = temp_dir.path().join("convex");
+    let shared_dir = temp_dir.path().join("shared");
+    fs::create_dir_all(convex_dir.join("_generated")).unwrap();
+    fs::create_dir(&shared_dir).unwrap();
+
+    fs::write(
+        convex_dir.join("schema.ts"),
+        "import { defineSchema } from \"convex/server\";\nimport { message } from \"./validators\";\nexport default \
+         defineSchema({});",
+    )
+    .unwrap();
+    // Imports are followed transitively, resolving `.js` specifiers to the `.ts` file
+    fs::write(convex_dir.join("validators.ts"), "export * from \"../shared/fields.js\";").unwrap();
+    fs::write(shared_dir.join("fields.ts"), "export const field = 1;").unwrap();
+    fs::write(convex_dir.join("_generated/api.ts"), "export const api = {};").unwrap();
+    fs::write(convex_dir.join("games.ts"), "import { api } from \"./_generated/api\";").unwrap();
+
+    let config = Configuration {
+        schema_path: convex_dir.join("schema.ts"),
+        function_paths: vec![convex_dir.join("games.ts")],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        config.watched_paths(),
+        vec![
+            convex_dir.join("schema.ts"),
+            convex_dir.join("games.ts"),
+            convex_dir.join("../shared/fields.ts"),
+            convex_dir.join("validators.ts"),
+            convex_dir.clone(),
+        ]
+    );
+}
+
 #[test]
 fn test_invalid_schema_syntax()
 {
@@ -75,7 +182,7 @@ fn test_missing_schema_file()
     };
 
     match generate(config) {
-        Err(ConvexTypeGeneratorError::MissingSchemaFile { .. }) => (),
+        Err(ConvexTypeGeneratorError::MissingSchemaFile) => (),
         other => panic!("Expected MissingSchemaFile error, got {:?}", other),
     }
 }
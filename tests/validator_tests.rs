@@ -0,0 +1,171 @@
+mod common;
+
+use std::fs;
+
+use common::object;
+use convex::Value as ConvexValue;
+use convex_typegen::errors::ArgumentErrorKind;
+use convex_typegen::parse_schema;
+use convex_typegen::validator::{parse_argument_error, v, Validator};
+use serde::Serialize;
+use tempdir::TempDir;
+
+fn posts_validator() -> Validator
+{
+    let temp_dir = TempDir::new("convex_validator_test").expect("Failed to create temp directory");
+    let schema_path = temp_dir.path().join("schema.ts");
+    fs::write(
+        &schema_path,
+        r#"
+        import { defineSchema, defineTable } from "convex/server";
+        import { v } from "convex/values";
+
+        export default defineSchema({
+            posts: defineTable({
+                author: v.id("users"),
+                status: v.union(v.literal("draft"), v.literal("published")),
+                subtitle: v.optional(v.string()),
+                deletedAt: v.union(v.number(), v.null()),
+                views: v.int64(),
+            }),
+        });
+    "#,
+    )
+    .unwrap();
+
+    let schema = parse_schema(schema_path).unwrap();
+    Validator::for_table(&schema.tables[0]).unwrap()
+}
+
+fn valid_post() -> Vec<(&'static str, ConvexValue)>
+{
+    vec![
+        ("author", ConvexValue::String("user_1".to_string())),
+        ("status", ConvexValue::String("draft".to_string())),
+        ("deletedAt", ConvexValue::Null),
+        ("views", ConvexValue::Int64(3)),
+    ]
+}
+
+#[test]
+fn test_valid_document()
+{
+    assert!(posts_validator().validate(&object(valid_post())).is_ok());
+}
+
+#[test]
+fn test_optional_is_not_nullable()
+{
+    let mut post = valid_post();
+    post.push(("subtitle", ConvexValue::Null));
+
+    let err = posts_validator().validate(&object(post)).unwrap_err();
+    assert_eq!(err.path, vec!["subtitle"]);
+    assert_eq!(err.expected, "v.string()");
+    assert_eq!(err.found, "null");
+}
+
+#[test]
+fn test_literal_and_numeric_types()
+{
+    let mut post = valid_post();
+    post[1].1 = ConvexValue::String("archived".to_string());
+    let err = posts_validator().validate(&object(post)).unwrap_err();
+    assert_eq!(err.path, vec!["status"]);
+    assert_eq!(err.found, "string \"archived\"");
+
+    let mut post = valid_post();
+    post[3].1 = ConvexValue::Float64(3.0);
+    let err = posts_validator().validate(&object(post)).unwrap_err();
+    assert_eq!(err.expected, "v.int64()");
+}
+
+#[test]
+fn test_missing_and_unknown_fields()
+{
+    let mut post = valid_post();
+    post.remove(0);
+    let err = posts_validator().validate(&object(post)).unwrap_err();
+    assert_eq!(err.path, vec!["author"]);
+    assert_eq!(err.found, "missing field");
+
+    let mut post = valid_post();
+    post.push(("title", ConvexValue::String("hello".to_string())));
+    let err = posts_validator().validate(&object(post)).unwrap_err();
+    assert_eq!(err.path, vec!["title"]);
+}
+
+#[test]
+fn test_id_table_names()
+{
+    let validator = posts_validator();
+    let post = object(valid_post());
+
+    assert!(validator
+        .validate_with_ids(&post, |id| id.split('_').next().map(|t| format!("{}s", t)))
+        .is_ok());
+
+    let err = validator
+        .validate_with_ids(&post, |_| Some("messages".to_string()))
+        .unwrap_err();
+    assert_eq!(err.path, vec!["author"]);
+    assert_eq!(err.found, "id for table \"messages\"");
+}
+
+#[test]
+fn test_validate_serialized()
+{
+    #[derive(Serialize)]
+    struct Args
+    {
+        name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        age: Option<f64>,
+    }
+
+    let validator = v::object([("name", v::string()), ("age", v::optional(v::number()))]);
+
+    assert!(validator
+        .validate_serialized(&Args {
+            name: "jamal".to_string(),
+            age: None,
+        })
+        .is_ok());
+    assert_eq!(
+        validator.to_string(),
+        "v.object({ age: v.optional(v.number()), name: v.string() })"
+    );
+}
+
+#[test]
+fn test_parse_argument_errors()
+{
+    let mismatch = parse_argument_error(
+        "[CONVEX M(users:create)] [Request ID: 8c1e2f] Server Error\nArgumentValidationError: Value does not match \
+         validator.\nPath: .addresses[1].zip\nValue: 94107.0\nValidator: v.string()\n\nCalled by client",
+    )
+    .unwrap();
+    assert_eq!(mismatch.function_path, "users:create");
+    assert_eq!(mismatch.kind, ArgumentErrorKind::Mismatch);
+    assert_eq!(mismatch.path, vec!["addresses", "1", "zip"]);
+    assert_eq!(mismatch.expected, "v.string()");
+    assert_eq!(mismatch.received, "94107.0");
+    assert_eq!(
+        mismatch.to_string(),
+        "Invalid arguments for 'users:create': 'addresses.1.zip' does not match v.string(), received 94107.0"
+    );
+
+    let extra = parse_argument_error(
+        "ArgumentValidationError: Object contains extra field `nickname` that is not in the validator.\nObject: {name: \
+         \"Ada\", nickname: \"A\"}\nValidator: v.object({name: v.string()})",
+    )
+    .unwrap();
+    assert_eq!(extra.function_path, "");
+    assert_eq!(extra.kind, ArgumentErrorKind::ExtraField);
+    assert_eq!(extra.path, vec!["nickname"]);
+    assert_eq!(extra.received, "{name: \"Ada\", nickname: \"A\"}");
+    assert_eq!(extra.expected, "v.object({name: v.string()})");
+
+    assert!(parse_argument_error("Uncaught Error: Not found").is_none());
+    assert!(parse_argument_error("ArgumentValidationError: something new").is_none());
+}
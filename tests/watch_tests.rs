@@ -0,0 +1,83 @@
+#![cfg(feature = "watch")]
+
+use std::fs;
+use std::ops::ControlFlow;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use convex_typegen::watch::{watch, WatchEvent};
+use convex_typegen::Configuration;
+use tempdir::TempDir;
+
+const SCHEMA: &str = r#"
+    import { defineSchema, defineTable } from "convex/server";
+    import { v } from "convex/values";
+
+    export default defineSchema({
+        games: defineTable({
+            win_count: v.number(),
+        }),
+    });
+"#;
+
+#[test]
+fn test_watch_regenerates_on_changes()
+{
+    let temp_dir = TempDir::new("convex_watch_test").expect("Failed to create temp directory");
+    let schema_path = temp_dir.path().join("schema.ts");
+    let function_path = temp_dir.path().join("games.ts");
+    let helper_path = temp_dir.path().join("helpers.ts");
+    let output_path = temp_dir.path().join("types.rs");
+
+    fs::write(&schema_path, SCHEMA).unwrap();
+    fs::write(
+        &function_path,
+        r#"
+        import { query } from "./_generated/server";
+        import { v } from "convex/values";
+        import { loadGame } from "./helpers";
+
+        export const getGame = query({
+            args: { id: v.id("games") },
+            handler: async (ctx, args) => loadGame(ctx, args.id),
+        });
+        "#,
+    )
+    .unwrap();
+    fs::write(&helper_path, "export const loadGame = async (ctx, id) => ctx.db.get(id);").unwrap();
+
+    let config = Configuration {
+        schema_path: schema_path.clone(),
+        out_file: output_path.to_string_lossy().to_string(),
+        function_paths: vec![function_path],
+        ..Default::default()
+    };
+
+    let (sender, events) = mpsc::channel();
+    std::thread::spawn(move || {
+        watch(config, |event| match sender.send(event) {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(_) => ControlFlow::Break(()),
+        })
+    });
+    let next_event = || events.recv_timeout(Duration::from_secs(10)).expect("No event while watching");
+
+    assert!(matches!(next_event(), WatchEvent::Generated { .. }));
+    assert!(fs::read_to_string(&output_path).unwrap().contains("pub win_count: f64"));
+
+    fs::write(&schema_path, SCHEMA.replace("win_count", "loss_count")).unwrap();
+    assert!(matches!(next_event(), WatchEvent::Generated { .. }));
+    assert!(fs::read_to_string(&output_path).unwrap().contains("pub loss_count: f64"));
+
+    // Parse errors are reported, and watching carries on
+    fs::write(&schema_path, "export default defineSchema({").unwrap();
+    assert!(matches!(next_event(), WatchEvent::Failed(_)));
+    assert!(fs::read_to_string(&output_path).unwrap().contains("pub loss_count: f64"));
+
+    fs::write(&schema_path, SCHEMA).unwrap();
+    assert!(matches!(next_event(), WatchEvent::Generated { .. }));
+
+    // Imported files are watched too, though they don't change the generated code here
+    fs::write(&helper_path, "export const loadGame = async (ctx, id) => null;").unwrap();
+    assert!(matches!(next_event(), WatchEvent::Unchanged { .. }));
+}